@@ -0,0 +1,106 @@
+use crate::buf::{fixed::registry::Registry, IoBuf, IoBufMut};
+use std::{cell::RefCell, fmt, mem::ManuallyDrop, ops, rc::Rc};
+
+/// A buffer checked out of a [`FixedBufRegistry`].
+///
+/// The buffer dereferences to its initialized bytes, like a `Vec<u8>`. It is
+/// returned to the registry when dropped.
+///
+/// [`FixedBufRegistry`]: crate::buf::fixed::FixedBufRegistry
+pub struct FixedBuf {
+    registry: Rc<RefCell<Registry>>,
+    index: u16,
+    buf: ManuallyDrop<Vec<u8>>,
+}
+
+impl FixedBuf {
+    pub(super) fn new(registry: Rc<RefCell<Registry>>, index: u16, buf: Vec<u8>) -> FixedBuf {
+        FixedBuf {
+            registry,
+            index,
+            buf: ManuallyDrop::new(buf),
+        }
+    }
+
+    /// Index of the buffer in its registry.
+    pub fn buf_index(&self) -> u16 {
+        self.index
+    }
+
+    /// Appends `src` to the initialized bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` does not fit in the remaining capacity. Fixed buffers
+    /// never grow, as that would move them out of the registered memory.
+    pub fn extend_from_slice(&mut self, src: &[u8]) {
+        assert!(
+            src.len() <= self.buf.capacity() - self.buf.len(),
+            "fixed buffer overflow"
+        );
+        self.buf.extend_from_slice(src);
+    }
+
+    /// Sets the number of initialized bytes to zero.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+}
+
+unsafe impl IoBuf for FixedBuf {
+    fn stable_ptr(&self) -> *const u8 {
+        self.buf.as_ptr()
+    }
+
+    fn bytes_init(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn bytes_total(&self) -> usize {
+        self.buf.capacity()
+    }
+}
+
+unsafe impl IoBufMut for FixedBuf {
+    fn stable_mut_ptr(&mut self) -> *mut u8 {
+        self.buf.as_mut_ptr()
+    }
+
+    unsafe fn set_init(&mut self, init_len: usize) {
+        if self.buf.len() < init_len {
+            self.buf.set_len(init_len);
+        }
+    }
+}
+
+impl ops::Deref for FixedBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl ops::DerefMut for FixedBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
+
+impl Drop for FixedBuf {
+    fn drop(&mut self) {
+        // Safety: the buffer is not used after this point.
+        let buf = unsafe { ManuallyDrop::take(&mut self.buf) };
+        self.registry.borrow_mut().check_in(self.index, buf);
+    }
+}
+
+impl fmt::Debug for FixedBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBuf")
+            .field("buf_index", &self.index)
+            .field("len", &self.buf.len())
+            .field("capacity", &self.buf.capacity())
+            .finish()
+    }
+}
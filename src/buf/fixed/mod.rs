@@ -0,0 +1,15 @@
+//! Buffers pre-registered with the kernel.
+//!
+//! Registering buffers with `IORING_REGISTER_BUFFERS` maps them into the
+//! kernel once, instead of pinning the pages on every operation. A
+//! [`FixedBufRegistry`] owns a set of such buffers and lends them out as
+//! [`FixedBuf`] values, which are passed to operations such as
+//! [`File::read_fixed_at`].
+//!
+//! [`File::read_fixed_at`]: crate::fs::File::read_fixed_at
+
+mod handle;
+pub use handle::FixedBuf;
+
+mod registry;
+pub use registry::FixedBufRegistry;
@@ -0,0 +1,174 @@
+use crate::{buf::fixed::FixedBuf, driver};
+use std::{cell::RefCell, fmt, io, rc::Rc};
+
+/// A set of buffers registered with the kernel.
+///
+/// The buffers are registered with the ring of the current runtime by
+/// [`register`], and can then be checked out as [`FixedBuf`] values. A checked
+/// out buffer returns to the registry when it is dropped, keeping its
+/// contents.
+///
+/// A ring holds at most one set of registered buffers at a time. The buffers
+/// are unregistered when the registry and all of its checked out buffers are
+/// dropped, or by calling [`unregister`].
+///
+/// [`register`]: FixedBufRegistry::register
+/// [`unregister`]: FixedBufRegistry::unregister
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_uring::{buf::fixed::FixedBufRegistry, fs::File};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     async_uring::start(async {
+///         let registry = FixedBufRegistry::new((0..4).map(|_| Vec::with_capacity(4096)));
+///         registry.register()?;
+///
+///         let file = File::open("hello.txt").await?;
+///
+///         let buf = registry.check_out(0).unwrap();
+///         let (res, buf) = file.read_fixed_at(buf, 0).await;
+///         let n = res?;
+///
+///         println!("{:?}", &buf[..n]);
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+#[derive(Clone)]
+pub struct FixedBufRegistry {
+    inner: Rc<RefCell<Registry>>,
+}
+
+pub(super) struct Registry {
+    /// Buffers not checked out, by index
+    bufs: Vec<Option<Vec<u8>>>,
+
+    /// Set while the buffers are registered with a ring
+    driver: Option<Rc<RefCell<driver::Inner>>>,
+}
+
+impl FixedBufRegistry {
+    /// Creates a registry from the given buffers.
+    ///
+    /// The buffers keep their contents. The whole capacity of each buffer is
+    /// registered, so that reads may fill it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u16::MAX` buffers are given, the limit of the
+    /// kernel.
+    pub fn new(bufs: impl IntoIterator<Item = Vec<u8>>) -> FixedBufRegistry {
+        let bufs: Vec<_> = bufs.into_iter().map(Some).collect();
+        assert!(bufs.len() <= u16::MAX as usize, "too many fixed buffers");
+
+        FixedBufRegistry {
+            inner: Rc::new(RefCell::new(Registry { bufs, driver: None })),
+        }
+    }
+
+    /// Registers the buffers with the ring of the current runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffers are already registered, if the ring
+    /// already has registered buffers, or if the buffers exceed the locked
+    /// memory limit of the process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime, or while buffers are checked
+    /// out.
+    pub fn register(&self) -> io::Result<()> {
+        let mut registry = self.inner.borrow_mut();
+
+        if registry.driver.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "buffers are already registered",
+            ));
+        }
+
+        let iovecs: Vec<_> = registry
+            .bufs
+            .iter_mut()
+            .map(|buf| {
+                let buf = buf.as_mut().expect("buffer is checked out");
+                libc::iovec {
+                    iov_base: buf.as_mut_ptr() as _,
+                    iov_len: buf.capacity(),
+                }
+            })
+            .collect();
+
+        let driver = driver::CURRENT.with(|inner_rc| inner_rc.clone());
+        driver
+            .borrow()
+            .uring
+            .submitter()
+            .register_buffers(&iovecs)?;
+
+        registry.driver = Some(driver);
+        Ok(())
+    }
+
+    /// Unregisters the buffers.
+    ///
+    /// Operations using buffers of the registry fail once the buffers are
+    /// unregistered. Does nothing if the buffers are not registered.
+    pub fn unregister(&self) -> io::Result<()> {
+        self.inner.borrow_mut().unregister()
+    }
+
+    /// Returns the number of buffers in the registry.
+    pub fn len(&self) -> usize {
+        self.inner.borrow().bufs.len()
+    }
+
+    /// Returns `true` if the registry holds no buffers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks out the buffer at `index`.
+    ///
+    /// Returns `None` if the buffer is already checked out, or if `index` is
+    /// out of bounds.
+    pub fn check_out(&self, index: usize) -> Option<FixedBuf> {
+        let buf = self.inner.borrow_mut().bufs.get_mut(index)?.take()?;
+        Some(FixedBuf::new(self.inner.clone(), index as u16, buf))
+    }
+}
+
+impl Registry {
+    /// Returns a buffer that was checked out.
+    pub(super) fn check_in(&mut self, index: u16, buf: Vec<u8>) {
+        self.bufs[index as usize] = Some(buf);
+    }
+
+    fn unregister(&mut self) -> io::Result<()> {
+        match self.driver.take() {
+            Some(driver) => driver.borrow().uring.submitter().unregister_buffers(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Registry {
+    fn drop(&mut self) {
+        // The kernel must not access the buffers after they are freed.
+        let _ = self.unregister();
+    }
+}
+
+impl fmt::Debug for FixedBufRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registry = self.inner.borrow();
+        f.debug_struct("FixedBufRegistry")
+            .field("len", &registry.bufs.len())
+            .field("registered", &registry.driver.is_some())
+            .finish()
+    }
+}
@@ -22,6 +22,7 @@ use std::ops;
 /// by `stable_ptr` must remain valid even if the `IoBuf` value is moved.
 ///
 /// [`slice()`]: IoBuf::slice
+#[allow(clippy::missing_safety_doc)]
 pub unsafe trait IoBuf: Unpin + 'static {
     /// Returns a raw pointer to the vector’s buffer.
     ///
@@ -10,6 +10,7 @@ use crate::buf::IoBuf;
 /// Buffers passed to `io-uring` operations must reference a stable memory
 /// region. While the runtime holds ownership to a buffer, the pointer returned
 /// by `stable_mut_ptr` must remain valid even if the `IoBufMut` value is moved.
+#[allow(clippy::missing_safety_doc)]
 pub unsafe trait IoBufMut: IoBuf {
     /// Returns a raw mutable pointer to the vector’s buffer.
     ///
@@ -4,6 +4,8 @@
 //! crate defines [`IoBuf`] and [`IoBufMut`] traits which are implemented by buffer
 //! types that respect the `io-uring` contract.
 
+pub mod fixed;
+
 mod io_buf;
 pub use io_buf::IoBuf;
 
@@ -13,6 +15,8 @@ pub use io_buf_mut::IoBufMut;
 mod slice;
 pub use slice::Slice;
 
+pub use crate::driver::{BufPool, ProvidedBuf};
+
 pub(crate) fn deref(buf: &impl IoBuf) -> &[u8] {
     // Safety: the `IoBuf` trait is marked as unsafe and is expected to be
     // implemented correctly.
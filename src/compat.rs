@@ -0,0 +1,313 @@
+//! Adapters to the `futures-io` traits.
+//!
+//! The types of this crate pass owned buffers to the kernel, which does not
+//! fit the borrowed buffers of [`AsyncRead`] and [`AsyncWrite`]. [`Compat`]
+//! bridges the two by copying through internal buffers, so that streams and
+//! files can be used with the wider ecosystem: codecs, TLS, `io::copy`, ...
+//!
+//! [`AsyncRead`]: futures_lite::io::AsyncRead
+//! [`AsyncWrite`]: futures_lite::io::AsyncWrite
+
+use crate::{
+    driver::{Op, Read, SharedFd, Shutdown, Write},
+    fs::File,
+};
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite};
+use std::{
+    future::Future,
+    io::{self, SeekFrom},
+    mem, net,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Capacity of the read and write buffers.
+const CAPACITY: usize = 8 * 1024;
+
+/// Wraps a stream or file to implement the `futures-io` traits.
+///
+/// `Compat` implements [`AsyncRead`], [`AsyncBufRead`] and [`AsyncWrite`],
+/// plus [`AsyncSeek`] for files. Created by `compat` methods, e.g.
+/// [`TcpStream::compat`].
+///
+/// Reads are buffered: each read operation fills an internal buffer, which is
+/// then copied out. Writes are buffered as well, and only reach the kernel
+/// once the buffer is full, or on [`flush`] or [`close`]. Closing a stream
+/// also shuts down its write half, while closing a file only flushes it.
+///
+/// A file is read and written from a cursor, which starts at offset 0 and is
+/// moved with [`AsyncSeek`]. Seeking from the end fails for fixed files, whose
+/// size cannot be queried.
+///
+/// [`AsyncRead`]: futures_lite::io::AsyncRead
+/// [`AsyncBufRead`]: futures_lite::io::AsyncBufRead
+/// [`AsyncWrite`]: futures_lite::io::AsyncWrite
+/// [`AsyncSeek`]: futures_lite::io::AsyncSeek
+/// [`flush`]: futures_lite::io::AsyncWriteExt::flush
+/// [`close`]: futures_lite::io::AsyncWriteExt::close
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_uring::net::TcpStream;
+/// use futures_lite::io::{AsyncBufReadExt, AsyncWriteExt};
+///
+/// fn main() -> std::io::Result<()> {
+///     async_uring::start(async {
+///         let stream = TcpStream::connect("127.0.0.1:6379").await?;
+///         let mut stream = stream.compat();
+///
+///         stream.write_all(b"PING\r\n").await?;
+///         stream.flush().await?;
+///
+///         let mut line = String::new();
+///         stream.read_line(&mut line).await?;
+///         println!("{}", line);
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct Compat<T> {
+    inner: T,
+
+    /// Returns the descriptor of `inner`.
+    fd: fn(&T) -> &SharedFd,
+
+    /// Offset of the next operation, for files.
+    pos: Option<u64>,
+
+    /// Data read ahead, consumed from `read_start`.
+    read_buf: Vec<u8>,
+    read_start: usize,
+    read_op: Option<Op<Read<Vec<u8>>>>,
+
+    /// Data written but not yet sent to the kernel.
+    write_buf: Vec<u8>,
+    write_op: Option<Op<Write<Vec<u8>>>>,
+
+    shutdown_op: Option<Op<Shutdown>>,
+}
+
+impl<T> Compat<T> {
+    /// With `seekable`, operations use a cursor, as for files.
+    pub(crate) fn new(inner: T, fd: fn(&T) -> &SharedFd, seekable: bool) -> Compat<T> {
+        Compat {
+            inner,
+            fd,
+            pos: seekable.then_some(0),
+            read_buf: Vec::with_capacity(CAPACITY),
+            read_start: 0,
+            read_op: None,
+            write_buf: Vec::with_capacity(CAPACITY),
+            write_op: None,
+            shutdown_op: None,
+        }
+    }
+
+    /// Returns a reference to the wrapped stream or file.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the wrapped stream or file.
+    ///
+    /// Buffered data is lost: flush the writes before unwrapping.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn fd(&self) -> &SharedFd {
+        (self.fd)(&self.inner)
+    }
+
+    /// Completes the in-flight read, if any, into the read buffer.
+    fn poll_read_op(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let op = match &mut self.read_op {
+            Some(op) => op,
+            None => return Poll::Ready(Ok(())),
+        };
+
+        let (res, buf) = ready!(op.poll_read(cx));
+        self.read_op = None;
+        self.read_buf = buf;
+        self.read_start = 0;
+
+        let n = res?;
+        if let Some(pos) = &mut self.pos {
+            *pos += n as u64;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Sends the whole write buffer to the kernel.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            if let Some(op) = &mut self.write_op {
+                let (res, buf) = ready!(op.poll_write(cx));
+                self.write_op = None;
+                // On error, the data stays buffered.
+                self.write_buf = buf;
+
+                let n = res?;
+                if n == 0 {
+                    return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+                }
+                self.write_buf.drain(..n);
+                if let Some(pos) = &mut self.pos {
+                    *pos += n as u64;
+                }
+            }
+
+            if self.write_buf.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+
+            let buf = mem::take(&mut self.write_buf);
+            let op = Op::write_at(self.fd(), buf, self.pos.unwrap_or(0))?;
+            self.write_op = Some(op);
+        }
+    }
+
+    /// Drops the data read ahead of a file cursor, moving the cursor back.
+    fn poll_discard_read(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_read_op(cx))?;
+
+        if let Some(pos) = &mut self.pos {
+            *pos -= (self.read_buf.len() - self.read_start) as u64;
+            self.read_buf.clear();
+            self.read_start = 0;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T: Unpin> AsyncBufRead for Compat<T> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        // A file reads from the cursor, after the buffered writes.
+        if this.pos.is_some() {
+            ready!(this.poll_drain(cx))?;
+        }
+
+        if this.read_start == this.read_buf.len() && this.read_op.is_none() {
+            let mut buf = mem::take(&mut this.read_buf);
+            buf.clear();
+            let op = Op::read_at(this.fd(), buf, this.pos.unwrap_or(0))?;
+            this.read_op = Some(op);
+        }
+
+        ready!(this.poll_read_op(cx))?;
+        Poll::Ready(Ok(&this.read_buf[this.read_start..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.read_start = (this.read_start + amt).min(this.read_buf.len());
+    }
+}
+
+impl<T: Unpin> AsyncRead for Compat<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<T: Unpin> AsyncWrite for Compat<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        // A file writes at the cursor, before the data read ahead.
+        if this.pos.is_some() {
+            ready!(this.poll_discard_read(cx))?;
+        }
+
+        if this.write_op.is_some() || this.write_buf.len() + buf.len() > CAPACITY {
+            ready!(this.poll_drain(cx))?;
+        }
+
+        let n = buf.len().min(CAPACITY - this.write_buf.len());
+        this.write_buf.extend_from_slice(&buf[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_drain(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+
+        if this.pos.is_some() {
+            return Poll::Ready(Ok(()));
+        }
+
+        if this.shutdown_op.is_none() {
+            this.shutdown_op = Some(Op::shutdown(this.fd(), net::Shutdown::Write)?);
+        }
+
+        let completion = ready!(Pin::new(this.shutdown_op.as_mut().unwrap()).poll(cx));
+        this.shutdown_op = None;
+        completion.result?;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for Compat<File> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+        ready!(this.poll_discard_read(cx))?;
+
+        let current = this.pos.unwrap_or(0);
+        let new = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => current.checked_add_signed(offset),
+            SeekFrom::End(offset) => file_len(this.fd())?.checked_add_signed(offset),
+        };
+
+        let new = new.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        this.pos = Some(new);
+        Poll::Ready(Ok(new))
+    }
+}
+
+/// Returns the size of a file.
+///
+/// Fixed files have no regular descriptor to `fstat`, and `statx` does not
+/// take a fixed one, so their size cannot be queried.
+fn file_len(fd: &SharedFd) -> io::Result<u64> {
+    if fd.fixed().is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "cannot seek from the end of a fixed file",
+        ));
+    }
+
+    let mut stat: libc::stat = unsafe { mem::zeroed() };
+    syscall!(fstat(fd.raw_fd(), &mut stat))?;
+    Ok(stat.st_size as u64)
+}
@@ -1,5 +1,10 @@
-use crate::driver::{Op, SharedFd};
-use std::{boxed::Box, io};
+use crate::driver::{MultiOp, Op, SharedFd};
+use futures_lite::{ready, StreamExt};
+use std::{
+    boxed::Box,
+    io,
+    task::{Context, Poll},
+};
 
 pub(crate) struct Accept {
     fd: SharedFd,
@@ -7,7 +12,9 @@ pub(crate) struct Accept {
 }
 
 impl Op<Accept> {
-    pub(crate) fn accept(fd: &SharedFd) -> io::Result<Op<Accept>> {
+    /// Submit an accept. With `fixed`, the connection is installed into a
+    /// free slot of the fixed-file table, and the result is the slot.
+    pub(crate) fn accept(fd: &SharedFd, fixed: bool) -> io::Result<Op<Accept>> {
         use io_uring::{opcode, types};
 
         let socket_addr = Box::new((
@@ -20,14 +27,97 @@ impl Op<Accept> {
                 socket_addr,
             },
             |accept| {
-                opcode::Accept::new(
-                    types::Fd(accept.fd.raw_fd()),
+                // Fixed slots are never inherited, the kernel rejects
+                // `O_CLOEXEC` for them.
+                let file_index = fixed.then(types::DestinationSlot::auto_target);
+                let flags = if fixed { 0 } else { libc::O_CLOEXEC };
+
+                with_target!(accept.fd, |fd| opcode::Accept::new(
+                    fd,
                     &mut accept.socket_addr.0 as *mut _ as *mut _,
                     &mut accept.socket_addr.1,
                 )
-                .flags(libc::O_CLOEXEC)
-                .build()
+                .flags(flags)
+                .file_index(file_index)
+                .build())
             },
         )
     }
 }
+
+pub(crate) struct AcceptMulti {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+}
+
+impl Op<AcceptMulti> {
+    /// Submit a multishot accept, completing once for every connection.
+    pub(crate) fn accept_multi(fd: &SharedFd) -> io::Result<MultiOp<AcceptMulti>> {
+        use io_uring::opcode;
+
+        let op = Op::submit_multi_with(AcceptMulti { fd: fd.clone() }, |accept| {
+            with_target!(accept.fd, |fd| opcode::AcceptMulti::new(fd)
+                .flags(libc::O_CLOEXEC)
+                .build())
+        })?;
+
+        // Connections accepted after the stream was dropped are closed.
+        Ok(op.on_orphaned(|_, result, _| {
+            if let Ok(fd) = result {
+                unsafe { libc::close(fd as i32) };
+            }
+        }))
+    }
+}
+
+/// Connections accepted by a multishot accept.
+///
+/// The operation is submitted on the first poll, and submitted again whenever
+/// the kernel stops it, e.g. after an error, until it is cancelled.
+pub(crate) struct Incoming {
+    fd: SharedFd,
+    op: Option<MultiOp<AcceptMulti>>,
+    cancelled: bool,
+}
+
+impl Incoming {
+    pub(crate) fn new(fd: &SharedFd) -> Incoming {
+        Incoming {
+            fd: fd.clone(),
+            op: None,
+            cancelled: false,
+        }
+    }
+
+    /// Stops accepting connections. Connections already accepted are still
+    /// yielded, then the stream ends.
+    pub(crate) fn cancel(&mut self) {
+        self.cancelled = true;
+
+        if let Some(op) = &mut self.op {
+            op.cancel();
+        }
+    }
+
+    pub(crate) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<io::Result<SharedFd>>> {
+        loop {
+            let op = match &mut self.op {
+                Some(op) => op,
+                None if self.cancelled => return Poll::Ready(None),
+                None => match Op::accept_multi(&self.fd) {
+                    Ok(op) => self.op.insert(op),
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                },
+            };
+
+            match ready!(op.poll_next(cx)) {
+                Some(cqe) => {
+                    return Poll::Ready(Some(cqe.result.map(|fd| SharedFd::new(fd as i32))));
+                }
+                None => self.op = None,
+            }
+        }
+    }
+}
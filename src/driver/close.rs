@@ -1,16 +1,20 @@
-use crate::driver::Op;
-use std::{io, os::unix::io::RawFd};
+use crate::driver::{shared_fd::Fd, Op};
+use std::io;
 
 pub(crate) struct Close {
-    fd: RawFd,
+    fd: Fd,
 }
 
 impl Op<Close> {
-    pub(crate) fn close(fd: RawFd) -> io::Result<Op<Close>> {
+    pub(crate) fn close(fd: Fd) -> io::Result<Op<Close>> {
         use io_uring::{opcode, types};
 
-        Op::try_submit_with(Close { fd }, |close| {
-            opcode::Close::new(types::Fd(close.fd)).build()
-        })
+        let op = Op::try_submit_with(Close { fd }, |close| match close.fd {
+            Fd::Raw(fd) => opcode::Close::new(types::Fd(fd)).build(),
+            Fd::Fixed(index) => opcode::Close::new(types::Fixed(index)).build(),
+        })?;
+
+        // A cancelled close would leak the descriptor.
+        Ok(op.keep_on_drop())
     }
 }
@@ -12,7 +12,7 @@ pub(crate) struct Connect {
 impl Op<Connect> {
     /// Submit a request to connect.
     pub(crate) fn connect(fd: &SharedFd, socket_addr: SockAddr) -> io::Result<Op<Connect>> {
-        use io_uring::{opcode, types};
+        use io_uring::opcode;
 
         Op::submit_with(
             Connect {
@@ -20,12 +20,12 @@ impl Op<Connect> {
                 socket_addr,
             },
             |connect| {
-                opcode::Connect::new(
-                    types::Fd(connect.fd.raw_fd()),
+                with_target!(connect.fd, |fd| opcode::Connect::new(
+                    fd,
                     connect.socket_addr.as_ptr(),
                     connect.socket_addr.len(),
                 )
-                .build()
+                .build())
             },
         )
     }
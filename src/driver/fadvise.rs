@@ -1,11 +1,13 @@
 use crate::driver::Op;
 use std::{io, os::unix::io::RawFd};
 
+#[allow(dead_code)]
 pub(crate) struct Fadvise {
     fd: RawFd,
 }
 
 impl Op<Fadvise> {
+    #[allow(dead_code)]
     pub(crate) fn fadvise(fd: RawFd, len: libc::off_t, advice: i32) -> io::Result<Self> {
         use io_uring::{opcode, types};
 
@@ -0,0 +1,34 @@
+use crate::driver::{Op, SharedFd};
+use std::{io, os::unix::io::RawFd};
+
+pub(crate) struct FilesUpdate {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    /// Descriptor to install, replaced by the allocated slot.
+    pub(crate) fds: Box<[RawFd; 1]>,
+}
+
+impl Op<FilesUpdate> {
+    /// Install a regular descriptor into a free slot of the fixed-file table.
+    pub(crate) fn install_fixed(fd: &SharedFd) -> io::Result<Op<FilesUpdate>> {
+        use io_uring::opcode;
+
+        Op::submit_with(
+            FilesUpdate {
+                fd: fd.clone(),
+                fds: Box::new([fd.raw_fd()]),
+            },
+            |update| {
+                opcode::FilesUpdate::new(update.fds.as_ptr(), 1)
+                    .offset(FILE_INDEX_ALLOC)
+                    .build()
+            },
+        )
+    }
+}
+
+/// `IORING_FILE_INDEX_ALLOC`, lets the kernel pick the slot.
+const FILE_INDEX_ALLOC: i32 = -1;
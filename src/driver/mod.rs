@@ -1,36 +1,81 @@
+/// Evaluates `$body` with `$target` bound to the descriptor of a `SharedFd`,
+/// as a `types::Fixed` for descriptors in the fixed-file table and as a
+/// `types::Fd` otherwise.
+macro_rules! with_target {
+    ($fd:expr, |$target:ident| $body:expr) => {
+        match $fd.fixed() {
+            Some(index) => {
+                let $target = io_uring::types::Fixed(index);
+                $body
+            }
+            None => {
+                let $target = io_uring::types::Fd($fd.raw_fd());
+                $body
+            }
+        }
+    };
+}
+
 mod accept;
 mod close;
 mod connect;
 mod fadvise;
+mod files_update;
 mod fsync;
 mod op;
 mod open;
+mod pool;
 mod read;
+mod read_fixed;
+mod read_pooled;
+mod readv;
 mod recv_from;
+mod recv_multi;
+mod send_msg;
 mod send_to;
+mod send_zc;
 mod shared_fd;
+mod shutdown;
 mod socket;
+mod timeout;
 mod unlink_at;
 mod util;
 mod write;
+mod write_fixed;
+mod writev;
 
+pub(crate) use accept::Incoming;
 pub(crate) use close::Close;
-pub(crate) use op::Op;
+pub(crate) use op::{MultiOp, Op};
+pub use pool::{BufPool, ProvidedBuf};
+pub(crate) use read::Read;
+pub(crate) use recv_from::RecvMeta;
+pub(crate) use recv_multi::RecvStream;
 pub(crate) use shared_fd::SharedFd;
+pub(crate) use shutdown::Shutdown;
 pub(crate) use socket::Socket;
+pub(crate) use timeout::Timeout;
+pub(crate) use write::Write;
 
-use io_uring::{cqueue, IoUring};
+use io_uring::{cqueue, opcode, squeue, types::SubmitArgs, IoUring};
 use scoped_tls::scoped_thread_local;
 use slab::Slab;
 use std::{
     cell::RefCell,
-    io,
+    io, mem,
     os::unix::io::{AsRawFd, RawFd},
     rc::Rc,
+    time::{Duration, Instant},
 };
 
 pub(crate) struct Driver {
     inner: Handle,
+
+    /// How long `shutdown` waits for cancelled operations
+    shutdown_timeout: Duration,
+
+    /// Set once `shutdown` ran, so dropping the driver does not wait again
+    shut_down: bool,
 }
 
 type Handle = Rc<RefCell<Inner>>;
@@ -39,26 +84,48 @@ pub(crate) struct Inner {
     /// In-flight operations
     ops: Ops,
 
+    /// Ignored operations that completed for the last time while the driver
+    /// is borrowed. Their data may own resources that submit new operations
+    /// when dropped (e.g. closing the last reference to a file descriptor),
+    /// so they are dropped once the borrow is released.
+    orphans: Vec<Box<dyn op::Orphan>>,
+
+    /// Allocated buffer group ids
+    buf_groups: Slab<()>,
+
     /// IoUring bindings
     pub(crate) uring: IoUring,
 }
 
-// When dropping the driver, all in-flight operations must have completed. This
-// type wraps the slab and ensures that, on drop, the slab is empty.
+// When dropping the driver, in-flight operations must either have completed or
+// be leaked, as the kernel may still access their buffers. This type wraps the
+// slab and leaks the data of operations still in the slab on drop.
 struct Ops(Slab<op::Lifecycle>);
 
 scoped_thread_local!(pub(crate) static CURRENT: Rc<RefCell<Inner>>);
 
 impl Driver {
-    pub(crate) fn new() -> io::Result<Driver> {
-        let uring = IoUring::new(256)?;
+    pub(crate) fn new(builder: &crate::Builder) -> io::Result<Driver> {
+        let uring = builder.build_uring()?;
+
+        if builder.fixed_files > 0 {
+            uring
+                .submitter()
+                .register_files_sparse(builder.fixed_files)?;
+        }
 
         let inner = Rc::new(RefCell::new(Inner {
             ops: Ops::new(),
+            orphans: Vec::new(),
+            buf_groups: Slab::new(),
             uring,
         }));
 
-        Ok(Driver { inner })
+        Ok(Driver {
+            inner,
+            shutdown_timeout: builder.shutdown_timeout,
+            shut_down: false,
+        })
     }
 
     /// Enter the driver context. This enables using uring types.
@@ -67,20 +134,79 @@ impl Driver {
     }
 
     pub(crate) fn tick(&self) {
-        let mut inner = self.inner.borrow_mut();
-        inner.tick();
+        let orphans = {
+            let mut inner = self.inner.borrow_mut();
+            inner.tick();
+            mem::take(&mut inner.orphans)
+        };
+
+        // Dropped outside of the borrow, see `Inner::orphans`.
+        drop(orphans);
     }
 
-    fn wait(&self) -> io::Result<usize> {
-        let mut inner = self.inner.borrow_mut();
-        let inner = &mut *inner;
+    /// Cancels all in-flight operations and waits up to the shutdown timeout
+    /// for their completions.
+    ///
+    /// Returns the number of operations still in flight once the timeout
+    /// elapsed. Their data is leaked when the driver is dropped.
+    pub(crate) fn shutdown(&mut self) -> usize {
+        self.shut_down = true;
+
+        let deadline = Instant::now() + self.shutdown_timeout;
+
+        // Tasks holding operations may already be gone, so every operation
+        // still tracked by the slab gets cancelled explicitly.
+        {
+            let mut inner = self.inner.borrow_mut();
+            let in_flight: Vec<_> = inner
+                .ops
+                .0
+                .iter()
+                .filter(|(_, lifecycle)| lifecycle.is_in_flight())
+                .map(|(index, _)| index as u64)
+                .collect();
+
+            for index in in_flight {
+                let _ = inner.submit_detached(opcode::AsyncCancel::new(index).build());
+            }
+        }
+
+        loop {
+            self.tick();
+
+            let in_flight = self.num_in_flight();
+            let now = Instant::now();
+
+            if in_flight == 0 || now >= deadline {
+                return in_flight;
+            }
+
+            let timeout = util::timespec(deadline - now);
+            let args = SubmitArgs::new().timespec(&timeout);
+
+            // Errors such as `ETIME` or `EINTR` only end the current wait, the
+            // deadline is checked again on the next iteration.
+            let _ = self
+                .inner
+                .borrow_mut()
+                .uring
+                .submitter()
+                .submit_with_args(1, &args);
+        }
+    }
 
-        inner.uring.submit_and_wait(1)
+    pub(crate) fn is_shut_down(&self) -> bool {
+        self.shut_down
     }
 
-    fn num_operations(&self) -> usize {
+    fn num_in_flight(&self) -> usize {
         let inner = self.inner.borrow();
-        inner.ops.0.len()
+        inner
+            .ops
+            .0
+            .iter()
+            .filter(|(_, lifecycle)| lifecycle.is_in_flight())
+            .count()
     }
 }
 
@@ -89,6 +215,9 @@ impl Inner {
         let mut cq = self.uring.completion();
         cq.sync();
 
+        // Multishot operations whose submitter fell behind
+        let mut throttled = Vec::new();
+
         for cqe in cq {
             if cqe.user_data() == u64::MAX {
                 // Result of the cancellation action. There isn't anything we
@@ -99,8 +228,45 @@ impl Inner {
 
             let index = cqe.user_data() as _;
 
-            self.ops.complete(index, resultify(&cqe), cqe.flags());
+            match self.ops.complete(index, resultify(&cqe), cqe.flags()) {
+                Some(op::Lifecycle::Ignored(orphan)) => self.orphans.push(orphan),
+                Some(_) => {}
+                None => {
+                    if self.ops.0[index].throttle() {
+                        throttled.push(index as u64);
+                    }
+                }
+            }
+        }
+
+        // The completions already queued are still consumed, and the
+        // submitter arms the operation again once it caught up.
+        for index in throttled {
+            let _ = self.submit_detached(opcode::AsyncCancel::new(index).build());
+        }
+    }
+
+    /// Submit an entry whose completion is not tracked by any operation.
+    ///
+    /// The completion is skipped by `tick`. Fails with `WouldBlock` if the
+    /// submission queue is still full once flushed, which happens with
+    /// `SQPOLL`, where flushing only wakes the polling thread.
+    pub(crate) fn submit_detached(&mut self, sqe: squeue::Entry) -> io::Result<()> {
+        if self.uring.submission().is_full() {
+            self.submit()?;
         }
+
+        let sqe = sqe.user_data(u64::MAX);
+
+        {
+            let mut sq = self.uring.submission();
+
+            if unsafe { sq.push(&sqe).is_err() } {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+        }
+
+        self.submit()
     }
 
     pub(crate) fn submit(&mut self) -> io::Result<()> {
@@ -129,11 +295,8 @@ impl AsRawFd for Driver {
 
 impl Drop for Driver {
     fn drop(&mut self) {
-        while self.num_operations() > 0 {
-            // If waiting fails, ignore the error. The wait will be attempted
-            // again on the next loop.
-            let _ = self.wait().unwrap();
-            self.tick();
+        if !self.shut_down {
+            self.shutdown();
         }
     }
 }
@@ -157,16 +320,29 @@ impl Ops {
         self.0.remove(index);
     }
 
-    fn complete(&mut self, index: usize, result: io::Result<u32>, flags: u32) {
+    // Complete an operation, returning it if it was removed from the slab
+    fn complete(
+        &mut self,
+        index: usize,
+        result: io::Result<u32>,
+        flags: u32,
+    ) -> Option<op::Lifecycle> {
         if self.0[index].complete(result, flags) {
-            self.0.remove(index);
+            Some(self.0.remove(index))
+        } else {
+            None
         }
     }
 }
 
 impl Drop for Ops {
     fn drop(&mut self) {
-        assert!(self.0.is_empty());
+        for lifecycle in self.0.drain() {
+            if let op::Lifecycle::Ignored(data) = lifecycle {
+                // The kernel may still write to buffers owned by the data.
+                mem::forget(data);
+            }
+        }
     }
 }
 
@@ -1,13 +1,16 @@
 use crate::driver;
 
-use io_uring::squeue;
+use futures_lite::Stream;
+use io_uring::{cqueue, squeue, types::Timespec};
 use std::{
     cell::RefCell,
+    collections::VecDeque,
     future::Future,
     io,
     pin::Pin,
     rc::Rc,
     task::{Context, Poll, Waker},
+    time::Duration,
 };
 
 /// In-flight operation
@@ -20,6 +23,42 @@ pub(crate) struct Op<T: 'static> {
 
     // Per-operation data
     data: Option<T>,
+
+    // Linked timeout, if the operation has a deadline
+    link_timeout: Option<LinkTimeout>,
+
+    // Whether a cancellation request was already submitted
+    cancelled: bool,
+
+    // Whether dropping the operation cancels it
+    cancel_on_drop: bool,
+
+    // Called with each completion the submitter did not consume, e.g. because
+    // it lost interest in the operation
+    on_orphaned: Option<fn(&mut T, io::Result<u32>, u32)>,
+}
+
+/// In-flight operation that may complete several times, such as a multishot
+/// accept.
+///
+/// Its completions are consumed through its `Stream` implementation, it cannot
+/// be awaited as a single completion.
+pub(crate) struct MultiOp<T: 'static>(Op<T>);
+
+/// Number of completions a multishot operation queues for its submitter before
+/// it is cancelled. The submitter arms it again once it caught up.
+const MULTI_QUEUE_LIMIT: usize = 64;
+
+/// Timeout linked to an operation with a deadline.
+///
+/// The timeout completes on its own slot of the slab: `ETIME` if it fired and
+/// cancelled the operation, `ECANCELED` otherwise.
+struct LinkTimeout {
+    // Read by the kernel until the timeout is submitted
+    timespec: Box<Timespec>,
+
+    // Timeout index in the slab
+    index: usize,
 }
 
 /// Operation completion. Returns stored state with the result of the operation.
@@ -31,6 +70,14 @@ pub(crate) struct Completion<T> {
     pub(crate) flags: u32,
 }
 
+/// A completion of an operation that may complete several times.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub(crate) struct Cqe {
+    pub(crate) result: io::Result<u32>,
+    pub(crate) flags: u32,
+}
+
 pub(crate) enum Lifecycle {
     /// The operation has been submitted to uring and is currently in-flight
     Submitted,
@@ -40,10 +87,44 @@ pub(crate) enum Lifecycle {
 
     /// The submitter no longer has interest in the operation result. The state
     /// must be passed to the driver and held until the operation completes.
-    Ignored(Box<dyn std::any::Any>),
+    Ignored(Box<dyn Orphan>),
 
     /// The operation has completed.
     Completed(io::Result<u32>, u32),
+
+    /// The operation produced completions flagged with `IORING_CQE_F_MORE`,
+    /// and may produce more.
+    Multi(Multi),
+}
+
+pub(crate) struct Multi {
+    /// Completions not consumed by the submitter yet
+    completions: VecDeque<(io::Result<u32>, u32)>,
+
+    /// Set once the queue reached `MULTI_QUEUE_LIMIT` and the operation is
+    /// being cancelled
+    throttled: bool,
+
+    /// Waker of the submitter, if it is waiting
+    waker: Option<Waker>,
+
+    /// Set once the last completion arrived
+    finished: bool,
+}
+
+/// State of an operation whose submitter lost interest in the result.
+pub(crate) trait Orphan {
+    /// Called by the driver for each completion of the operation. The driver
+    /// is borrowed while this runs.
+    fn complete(&mut self, result: io::Result<u32>, flags: u32);
+}
+
+struct Orphaned<T> {
+    data: Option<T>,
+    // Read by the kernel until the operation completes
+    #[allow(dead_code)]
+    link_timeout: Option<Box<Timespec>>,
+    on_complete: Option<fn(&mut T, io::Result<u32>, u32)>,
 }
 
 impl<T> Op<T> {
@@ -53,9 +134,29 @@ impl<T> Op<T> {
             driver: inner_rc.clone(),
             index: inner.ops.insert(),
             data: Some(data),
+            link_timeout: None,
+            cancelled: false,
+            cancel_on_drop: true,
+            on_orphaned: None,
         }
     }
 
+    /// Set a function to call with each completion that is not consumed
+    /// because the operation was dropped, e.g. to recycle a buffer the kernel
+    /// selected for it. The function runs while the driver is borrowed.
+    pub(super) fn on_orphaned(mut self, f: fn(&mut T, io::Result<u32>, u32)) -> Op<T> {
+        self.on_orphaned = Some(f);
+        self
+    }
+
+    /// Let the operation run to completion when dropped, instead of
+    /// cancelling it. Used for operations that must not be interrupted, such
+    /// as closing a descriptor.
+    pub(super) fn keep_on_drop(mut self) -> Op<T> {
+        self.cancel_on_drop = false;
+        self
+    }
+
     /// Submit an operation to uring.
     ///
     /// `state` is stored during the operation tracking any state submitted to
@@ -64,28 +165,89 @@ impl<T> Op<T> {
     where
         F: FnOnce(&mut T) -> squeue::Entry,
     {
+        Op::submit_with_deadline(data, None, f)
+    }
+
+    /// Submit an operation to uring, linked to a timeout if `deadline` is
+    /// set.
+    ///
+    /// When the timeout fires first, the kernel cancels the operation and it
+    /// completes with `ErrorKind::TimedOut`. Operations cancelled for another
+    /// reason keep their `ECANCELED` error.
+    pub(super) fn submit_with_deadline<F>(
+        data: T,
+        deadline: Option<Duration>,
+        f: F,
+    ) -> io::Result<Op<T>>
+    where
+        F: FnOnce(&mut T) -> squeue::Entry,
+    {
+        use io_uring::opcode;
+
         driver::CURRENT.with(|inner_rc| {
             let mut inner_ref = inner_rc.borrow_mut();
             let inner = &mut *inner_ref;
 
-            // If the submission queue is full, flush it to the kernel
-            if inner.uring.submission().is_full() {
-                inner.submit()?;
-            }
-
             // Create the operation
             let mut op = Op::new(data, inner, inner_rc);
 
             // Configure the SQE
-            let sqe = f(op.data.as_mut().unwrap()).user_data(op.index as _);
+            let mut sqe = f(op.data.as_mut().unwrap()).user_data(op.index as _);
 
-            {
-                let mut sq = inner.uring.submission();
+            let mut timeout = None;
+            if let Some(deadline) = deadline {
+                let timespec = Box::new(driver::util::timespec(deadline));
+                let index = inner.ops.insert();
+                sqe = sqe.flags(squeue::Flags::IO_LINK);
+
+                timeout = Some(
+                    opcode::LinkTimeout::new(&*timespec)
+                        .build()
+                        .user_data(index as _),
+                );
+                op.link_timeout = Some(LinkTimeout { timespec, index });
+            }
+
+            // Push the new operation. A linked timeout must directly follow its
+            // operation, so both are pushed together or not at all. If the
+            // submission queue cannot hold them, flush it to the kernel. With
+            // `SQPOLL`, flushing only wakes the polling thread, so the queue
+            // may still be full afterwards.
+            let mut flushed = false;
+            let pushed = loop {
+                let res = {
+                    let mut sq = inner.uring.submission();
+                    match &timeout {
+                        Some(timeout) => unsafe {
+                            sq.push_multiple(&[sqe.clone(), timeout.clone()])
+                        },
+                        None => unsafe { sq.push(&sqe) },
+                    }
+                };
+
+                if res.is_ok() {
+                    break Ok(());
+                }
+                if flushed {
+                    break Err(io::ErrorKind::WouldBlock.into());
+                }
+                if let Err(e) = inner.submit() {
+                    break Err(e);
+                }
+                flushed = true;
+            };
 
-                // Push the new operation
-                if unsafe { sq.push(&sqe).is_err() } {
-                    unimplemented!("when is this hit?");
+            if let Err(e) = pushed {
+                // Nothing reached the kernel, release the slots.
+                inner.ops.remove(op.index);
+                if let Some(timeout) = op.link_timeout.take() {
+                    inner.ops.remove(timeout.index);
                 }
+                op.index = usize::MAX;
+
+                drop(inner_ref);
+                drop(op);
+                return Err(e);
             }
 
             // Submit the new operation. At this point, the operation has been
@@ -98,6 +260,45 @@ impl<T> Op<T> {
         })
     }
 
+    /// Ask the kernel to cancel the operation, if it is still in-flight.
+    ///
+    /// The operation completes early, usually with `ECANCELED`. Operations
+    /// the kernel already started may still complete normally, so the result
+    /// must be awaited as usual.
+    pub(crate) fn cancel(&mut self) {
+        use io_uring::opcode;
+
+        self.cancel_with(|index| opcode::AsyncCancel::new(index as _).build());
+    }
+
+    /// Submit the cancellation request built by `f` from the operation index,
+    /// unless the operation already completed or was cancelled.
+    pub(super) fn cancel_with<F>(&mut self, f: F)
+    where
+        F: FnOnce(usize) -> squeue::Entry,
+    {
+        let mut inner = self.driver.borrow_mut();
+
+        match inner.ops.get_mut(self.index) {
+            Some(lifecycle) if lifecycle.is_in_flight() && !self.cancelled => {}
+            _ => return,
+        }
+
+        self.cancelled = true;
+
+        // If the request cannot be submitted, the operation stays in-flight
+        // until it completes on its own.
+        let _ = inner.submit_detached(f(self.index));
+    }
+
+    /// Submit an operation that may complete several times to uring.
+    pub(super) fn submit_multi_with<F>(data: T, f: F) -> io::Result<MultiOp<T>>
+    where
+        F: FnOnce(&mut T) -> squeue::Entry,
+    {
+        Op::submit_with(data, f).map(MultiOp)
+    }
+
     /// Try submitting an operation to uring
     pub(super) fn try_submit_with<F>(data: T, f: F) -> io::Result<Op<T>>
     where
@@ -137,8 +338,42 @@ where
                 *lifecycle = Lifecycle::Waiting(waker);
                 Poll::Pending
             }
-            Lifecycle::Ignored(..) => unreachable!(),
-            Lifecycle::Completed(result, flags) => {
+            // Operations completing several times are submitted as `MultiOp`.
+            Lifecycle::Ignored(..) | Lifecycle::Multi(..) => unreachable!(),
+            Lifecycle::Completed(mut result, flags) => {
+                if let Some(timeout) = &me.link_timeout {
+                    // Only the completion of the timeout tells whether it
+                    // fired, so wait for it as well.
+                    let timeout_lifecycle = inner
+                        .ops
+                        .get_mut(timeout.index)
+                        .expect("invalid internal state");
+
+                    let fired = match mem::replace(timeout_lifecycle, Lifecycle::Submitted) {
+                        Lifecycle::Completed(res, _) => {
+                            res.err().and_then(|e| e.raw_os_error()) == Some(libc::ETIME)
+                        }
+                        _ => {
+                            *timeout_lifecycle = Lifecycle::Waiting(cx.waker().clone());
+                            *inner.ops.get_mut(me.index).unwrap() =
+                                Lifecycle::Completed(result, flags);
+                            return Poll::Pending;
+                        }
+                    };
+
+                    inner.ops.remove(timeout.index);
+                    me.link_timeout = None;
+
+                    // The kernel cancels operations whose linked timeout fired.
+                    if fired && !me.cancelled {
+                        if let Err(ref e) = result {
+                            if e.raw_os_error() == Some(libc::ECANCELED) {
+                                result = Err(io::ErrorKind::TimedOut.into());
+                            }
+                        }
+                    }
+                }
+
                 inner.ops.remove(me.index);
                 me.index = usize::MAX;
 
@@ -152,22 +387,164 @@ where
     }
 }
 
+impl<T> MultiOp<T> {
+    /// See [`Op::on_orphaned`].
+    pub(super) fn on_orphaned(self, f: fn(&mut T, io::Result<u32>, u32)) -> MultiOp<T> {
+        MultiOp(self.0.on_orphaned(f))
+    }
+
+    /// See [`Op::cancel`]. The stream ends without yielding the cancellation.
+    pub(crate) fn cancel(&mut self) {
+        self.0.cancel();
+    }
+
+    /// Returns the data of an operation whose completions were all consumed.
+    pub(super) fn into_data(mut self) -> T {
+        assert_eq!(self.0.index, usize::MAX, "operation still in flight");
+        self.0.data.take().expect("unexpected operation state")
+    }
+}
+
+/// Yields every completion of an operation flagged with `IORING_CQE_F_MORE`,
+/// such as a multishot accept. Ends after the completion without the flag.
+///
+/// Operations completing once yield a single completion. Once the operation
+/// was cancelled, explicitly or because the submitter fell behind, the stream
+/// ends without yielding the cancellation.
+impl<T> Stream for MultiOp<T>
+where
+    T: Unpin + 'static,
+{
+    type Item = Cqe;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        use std::mem;
+
+        let me = &mut self.0;
+        let mut inner = me.driver.borrow_mut();
+        let lifecycle = match inner.ops.get_mut(me.index) {
+            Some(lifecycle) => lifecycle,
+            None => return Poll::Ready(None),
+        };
+
+        match mem::replace(lifecycle, Lifecycle::Submitted) {
+            Lifecycle::Submitted | Lifecycle::Waiting(_) => {
+                *lifecycle = Lifecycle::Waiting(cx.waker().clone());
+                Poll::Pending
+            }
+            Lifecycle::Completed(result, flags) => {
+                inner.ops.remove(me.index);
+                me.index = usize::MAX;
+
+                match result {
+                    Err(e) if me.cancelled && e.raw_os_error() == Some(libc::ECANCELED) => {
+                        Poll::Ready(None)
+                    }
+                    result => Poll::Ready(Some(Cqe { result, flags })),
+                }
+            }
+            Lifecycle::Multi(mut multi) => match multi.completions.pop_front() {
+                Some((Err(e), _))
+                    if (multi.throttled || me.cancelled)
+                        && multi.finished
+                        && multi.completions.is_empty()
+                        && e.raw_os_error() == Some(libc::ECANCELED) =>
+                {
+                    inner.ops.remove(me.index);
+                    me.index = usize::MAX;
+                    Poll::Ready(None)
+                }
+                Some((result, flags)) => {
+                    *lifecycle = Lifecycle::Multi(multi);
+                    Poll::Ready(Some(Cqe { result, flags }))
+                }
+                None if multi.finished => {
+                    inner.ops.remove(me.index);
+                    me.index = usize::MAX;
+                    Poll::Ready(None)
+                }
+                None => {
+                    multi.waker = Some(cx.waker().clone());
+                    *lifecycle = Lifecycle::Multi(multi);
+                    Poll::Pending
+                }
+            },
+            Lifecycle::Ignored(..) => unreachable!(),
+        }
+    }
+}
+
 impl<T> Drop for Op<T> {
     fn drop(&mut self) {
+        use std::mem;
+
+        // Nobody is interested in the result any more, stop the operation
+        // instead of waiting for it to complete on its own.
+        if self.cancel_on_drop {
+            self.cancel();
+        }
+
         let mut inner = self.driver.borrow_mut();
+
+        // The timeout completes right after the operation it is linked to.
+        if let Some(timeout) = self.link_timeout.take() {
+            let lifecycle = inner.ops.get_mut(timeout.index).unwrap();
+
+            if lifecycle.is_in_flight() {
+                *lifecycle = Lifecycle::Ignored(Box::new(Orphaned::<()> {
+                    data: None,
+                    link_timeout: Some(timeout.timespec),
+                    on_complete: None,
+                }));
+            } else {
+                inner.ops.remove(timeout.index);
+            }
+        }
+
         let lifecycle = match inner.ops.get_mut(self.index) {
             Some(lifecycle) => lifecycle,
             None => return,
         };
 
-        match lifecycle {
-            Lifecycle::Submitted | Lifecycle::Waiting(_) => {
-                *lifecycle = Lifecycle::Ignored(Box::new(self.data.take()));
+        let mut orphan = Box::new(Orphaned {
+            data: self.data.take(),
+            link_timeout: None,
+            on_complete: self.on_orphaned,
+        });
+
+        // Completions the submitter did not consume go to the hook. Whatever
+        // the kernel still produces is handled by the driver.
+        let in_flight = match mem::replace(lifecycle, Lifecycle::Submitted) {
+            Lifecycle::Submitted | Lifecycle::Waiting(_) => true,
+            Lifecycle::Multi(multi) => {
+                for (result, flags) in multi.completions {
+                    orphan.complete(result, flags);
+                }
+                !multi.finished
             }
-            Lifecycle::Completed(..) => {
-                inner.ops.remove(self.index);
+            Lifecycle::Completed(result, flags) => {
+                orphan.complete(result, flags);
+                false
             }
             Lifecycle::Ignored(..) => unreachable!(),
+        };
+
+        if in_flight {
+            *lifecycle = Lifecycle::Ignored(orphan);
+        } else {
+            inner.ops.remove(self.index);
+            drop(inner);
+
+            // The data may submit operations when dropped.
+            drop(orphan);
+        }
+    }
+}
+
+impl<T> Orphan for Orphaned<T> {
+    fn complete(&mut self, result: io::Result<u32>, flags: u32) {
+        if let (Some(data), Some(f)) = (self.data.as_mut(), self.on_complete) {
+            f(data, result, flags);
         }
     }
 }
@@ -176,7 +553,18 @@ impl Lifecycle {
     pub(super) fn complete(&mut self, result: io::Result<u32>, flags: u32) -> bool {
         use std::mem;
 
+        let more = cqueue::more(flags);
+
         match mem::replace(self, Lifecycle::Submitted) {
+            Lifecycle::Submitted if more => {
+                *self = Lifecycle::Multi(Multi::new(result, flags, None));
+                false
+            }
+            Lifecycle::Waiting(waker) if more => {
+                *self = Lifecycle::Multi(Multi::new(result, flags, None));
+                waker.wake();
+                false
+            }
             Lifecycle::Submitted => {
                 *self = Lifecycle::Completed(result, flags);
                 false
@@ -186,8 +574,59 @@ impl Lifecycle {
                 waker.wake();
                 false
             }
-            Lifecycle::Ignored(..) => true,
+            Lifecycle::Multi(mut multi) => {
+                multi.completions.push_back((result, flags));
+                multi.finished = !more;
+                if let Some(waker) = multi.waker.take() {
+                    waker.wake();
+                }
+                *self = Lifecycle::Multi(multi);
+                false
+            }
+            Lifecycle::Ignored(mut orphan) => {
+                orphan.complete(result, flags);
+
+                // Once removed, the driver drops it outside of its borrow.
+                *self = Lifecycle::Ignored(orphan);
+                !more
+            }
             Lifecycle::Completed(..) => unreachable!("invalid operation state"),
         }
     }
+
+    /// Returns `true` the first time the completion queue of a multishot
+    /// operation is full, in which case the operation must be cancelled.
+    pub(super) fn throttle(&mut self) -> bool {
+        match self {
+            Lifecycle::Multi(multi)
+                if !multi.finished
+                    && !multi.throttled
+                    && multi.completions.len() >= MULTI_QUEUE_LIMIT =>
+            {
+                multi.throttled = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the kernel has not completed the operation yet.
+    pub(super) fn is_in_flight(&self) -> bool {
+        match self {
+            Lifecycle::Completed(..) => false,
+            Lifecycle::Multi(multi) => !multi.finished,
+            _ => true,
+        }
+    }
+}
+
+impl Multi {
+    fn new(result: io::Result<u32>, flags: u32, waker: Option<Waker>) -> Multi {
+        Multi {
+            completions: VecDeque::from([(result, flags)]),
+            throttled: false,
+            waker,
+            finished: false,
+        }
+    }
 }
@@ -17,7 +17,13 @@ impl Op<Open> {
     pub(crate) fn open(path: impl AsRef<Path>, options: &OpenOptions) -> io::Result<Op<Open>> {
         use io_uring::{opcode, types};
         let path = driver::util::cstr(path.as_ref())?;
-        let flags = libc::O_CLOEXEC | options.access_mode()? | options.creation_mode()?;
+        let mut flags = options.access_mode()? | options.creation_mode()?;
+
+        // Fixed slots are never inherited, the kernel rejects `O_CLOEXEC` for
+        // them.
+        if !options.fixed {
+            flags |= libc::O_CLOEXEC;
+        }
 
         Op::submit_with(Open { path, flags }, |open| {
             // Get a reference to the memory. The string will be held by the
@@ -25,9 +31,13 @@ impl Op<Open> {
             // completes.
             let p_ref = open.path.as_c_str().as_ptr();
 
+            // Let the kernel pick a free slot of the fixed-file table.
+            let file_index = options.fixed.then(types::DestinationSlot::auto_target);
+
             opcode::OpenAt::new(types::Fd(libc::AT_FDCWD), p_ref)
                 .flags(flags)
                 .mode(options.mode)
+                .file_index(file_index)
                 .build()
         })
     }
@@ -1,78 +1,336 @@
 use crate::driver;
 
-use io_uring::{opcode, IoUring};
-use std::io;
-use std::mem::ManuallyDrop;
+use io_uring::types::BufRingEntry;
+use std::{
+    cell::{Cell, RefCell},
+    fmt, io, ops, ptr,
+    rc::Rc,
+    slice,
+    sync::atomic::{AtomicU16, Ordering},
+};
+
+/// A group of buffers the kernel picks from when an operation completes.
+///
+/// Operations such as [`TcpStream::read_pooled`] do not own a buffer while
+/// they are pending. Once data arrives, the kernel selects a free buffer of
+/// the pool and the operation returns it as a [`ProvidedBuf`]. Dropping the
+/// `ProvidedBuf` hands the buffer back to the kernel.
+///
+/// This allows many idle reads to share a small amount of memory. When all
+/// buffers are in use, pooled operations fail with `ENOBUFS`.
+///
+/// The pool is backed by a buffer ring (`IORING_REGISTER_PBUF_RING`), which
+/// requires Linux 5.19. It is bound to the runtime it was created in.
+///
+/// [`TcpStream::read_pooled`]: crate::net::TcpStream::read_pooled
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_uring::{buf::BufPool, net::TcpStream};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     async_uring::start(async {
+///         let pool = BufPool::new(64, 4096)?;
+///
+///         let stream = TcpStream::connect("127.0.0.1:8080").await?;
+///
+///         while let Some(buf) = stream.read_pooled(&pool).await? {
+///             println!("{:?}", &buf[..]);
+///         }
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BufPool {
+    inner: Rc<Pool>,
+}
+
+/// A buffer selected by the kernel from a [`BufPool`].
+///
+/// The buffer dereferences to the bytes written by the operation. It is given
+/// back to the pool when dropped.
+pub struct ProvidedBuf {
+    pool: Rc<Pool>,
+
+    /// `None` for an empty datagram, received without selecting a buffer
+    bid: Option<u16>,
+    offset: usize,
+    len: usize,
+}
 
-/// Buffer pool shared with kernel
 pub(crate) struct Pool {
-    mem: *mut u8,
-    num: usize,
+    driver: Rc<RefCell<driver::Inner>>,
+
+    /// Buffer group id
+    bgid: u16,
+
+    /// Memory shared with the kernel, holding `entries` ring entries
+    ring: *mut BufRingEntry,
+    ring_size: usize,
+
+    /// Ring entry count minus one, the count is a power of two
+    mask: u16,
+
+    /// Local copy of the ring tail
+    tail: Cell<u16>,
+
+    /// Buffer memory, `num` buffers of `size` bytes
+    mem: Vec<u8>,
+    num: u16,
     size: usize,
 }
 
-pub(crate) struct ProvidedBuf {
-    buf: ManuallyDrop<Vec<u8>>,
-    driver: driver::Handle,
-}
+impl BufPool {
+    /// Creates a pool of `num` buffers of `size` bytes each, and registers it
+    /// with the ring of the current runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the kernel does not support buffer rings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime, if `num` is zero or larger than
+    /// 32768, or if `size` does not fit in a `u32`.
+    pub fn new(num: u16, size: usize) -> io::Result<BufPool> {
+        assert!(num > 0 && num <= 1 << 15, "invalid number of buffers");
+        assert!(u32::try_from(size).is_ok(), "buffer size too large");
 
-impl Pool {
-    pub(super) fn new(num: usize, size: usize) -> Pool {
-        let total = num * size;
-        let mut mem = ManuallyDrop::new(Vec::<u8>::with_capacity(total));
+        let driver = driver::CURRENT.with(|inner_rc| inner_rc.clone());
+        let entries = num.next_power_of_two();
 
-        assert_eq!(mem.capacity(), total);
+        // The ring must be page aligned, which `mmap` guarantees.
+        let ring_size = entries as usize * std::mem::size_of::<BufRingEntry>();
+        let ring = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                ring_size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_ANONYMOUS | libc::MAP_PRIVATE,
+                -1,
+                0,
+            )
+        };
+
+        if ring == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
 
-        Pool {
-            mem: mem.as_mut_ptr(),
+        let bgid = driver.borrow_mut().buf_groups.insert(());
+        let bgid = match u16::try_from(bgid) {
+            Ok(bgid) => bgid,
+            Err(_) => {
+                driver.borrow_mut().buf_groups.remove(bgid);
+                unsafe { libc::munmap(ring, ring_size) };
+                return Err(io::Error::other("too many buffer pools"));
+            }
+        };
+
+        // Dropping the pool on error unregisters and releases everything.
+        let pool = Pool {
+            driver,
+            bgid,
+            ring: ring as *mut BufRingEntry,
+            ring_size,
+            mask: entries - 1,
+            tail: Cell::new(0),
+            mem: Vec::with_capacity(num as usize * size),
             num,
             size,
+        };
+
+        pool.driver
+            .borrow()
+            .uring
+            .submitter()
+            .register_buf_ring(ring as u64, entries, bgid)?;
+
+        for bid in 0..num {
+            pool.provide(bid);
         }
+
+        Ok(BufPool {
+            inner: Rc::new(pool),
+        })
     }
 
-    pub(super) fn provide_buffers(&self, uring: &mut IoUring) -> io::Result<()> {
-        let op = opcode::ProvideBuffers::new(self.mem, self.size as _, self.num as _, 0, 0)
-            .build()
-            .user_data(0);
+    /// Returns the buffer group id used in submissions.
+    pub fn buf_group(&self) -> u16 {
+        self.inner.bgid
+    }
 
-        // Scoped to ensure `sq` drops before trying to submit
-        {
-            let mut sq = uring.submission();
+    /// Returns the number of buffers in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.num as usize
+    }
 
-            if unsafe { sq.push(&op) }.is_err() {
-                unimplemented!("when is this hit?");
-            }
+    /// Returns `true` if the pool has no buffers. Pools always have at least
+    /// one buffer.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns the size of each buffer.
+    pub fn buf_size(&self) -> usize {
+        self.inner.size
+    }
+
+    pub(crate) fn pool(&self) -> &Rc<Pool> {
+        &self.inner
+    }
+}
+
+impl Pool {
+    /// Returns the buffer `bid`, filled with `len` bytes by the kernel.
+    pub(crate) fn take(self: &Rc<Self>, bid: u16, len: usize) -> ProvidedBuf {
+        assert!(bid < self.num && len <= self.size);
+
+        ProvidedBuf {
+            pool: self.clone(),
+            bid: Some(bid),
+            offset: 0,
+            len,
         }
+    }
+
+    /// Returns an empty buffer that holds no buffer of the pool, for an
+    /// empty datagram: the kernel does not select a buffer to receive it.
+    pub(crate) fn take_empty(self: &Rc<Self>) -> ProvidedBuf {
+        ProvidedBuf {
+            pool: self.clone(),
+            bid: None,
+            offset: 0,
+            len: 0,
+        }
+    }
 
-        uring.submit_and_wait(1)?;
+    /// Gives the buffer `bid` back to the kernel.
+    pub(crate) fn provide(&self, bid: u16) {
+        let tail = self.tail.get();
 
-        let mut cq = uring.completion();
-        for cqe in &mut cq {
-            assert_eq!(cqe.user_data(), 0);
+        // Safety: the index is masked to the ring size. The tail field shares
+        // memory with the first entry, but is not touched by the setters.
+        unsafe {
+            let entry = &mut *self.ring.add((tail & self.mask) as usize);
+            entry.set_addr(self.mem.as_ptr().add(bid as usize * self.size) as u64);
+            entry.set_len(self.size as u32);
+            entry.set_bid(bid);
         }
 
-        Ok(())
+        let tail = tail.wrapping_add(1);
+        self.tail.set(tail);
+
+        // Publish the entry to the kernel.
+        unsafe {
+            let ptr = BufRingEntry::tail(self.ring) as *const AtomicU16;
+            (*ptr).store(tail, Ordering::Release);
+        }
     }
-}
 
-impl ProvidedBuf {}
+    fn buf(&self, bid: u16, offset: usize, len: usize) -> &[u8] {
+        // Safety: the kernel wrote `offset + len` bytes to the buffer, and does
+        // not access it until it is provided again.
+        unsafe {
+            slice::from_raw_parts(
+                self.mem.as_ptr().add(bid as usize * self.size + offset),
+                len,
+            )
+        }
+    }
+}
 
-impl Drop for ProvidedBuf {
+impl Drop for Pool {
     fn drop(&mut self) {
-        let mut driver = self.driver.borrow_mut();
-        let pool = &driver.pool;
+        // Operations using the pool hold a reference to it, so none are in
+        // flight anymore.
+        let unregistered = match self.driver.try_borrow_mut() {
+            Ok(mut driver) => {
+                let res = driver.uring.submitter().unregister_buf_ring(self.bgid);
+                if res.is_ok() {
+                    driver.buf_groups.remove(self.bgid as usize);
+                }
+                res.is_ok()
+            }
+            Err(_) => false,
+        };
+
+        // The kernel still references a ring it was not told about, so the
+        // ring and the buffers are leaked rather than freed.
+        if !unregistered {
+            std::mem::forget(std::mem::take(&mut self.mem));
+            return;
+        }
+
+        unsafe {
+            libc::munmap(self.ring as *mut libc::c_void, self.ring_size);
+        }
+    }
+}
+
+impl ProvidedBuf {
+    /// Returns the number of bytes in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the id of the buffer within its pool, or `None` for an empty
+    /// datagram, which the kernel receives without selecting a buffer.
+    pub fn buf_id(&self) -> Option<u16> {
+        self.bid
+    }
 
-        let ptr = self.buf.as_mut_ptr();
-        let bid = (ptr as usize - pool.mem as usize) / pool.size;
+    /// Restricts the buffer to `len` bytes starting at `offset`, e.g. to skip
+    /// a header the kernel wrote before the data.
+    pub(crate) fn narrow(&mut self, offset: usize, len: usize) {
+        assert!(offset + len <= self.len);
 
-        let op = opcode::ProvideBuffers::new(ptr, pool.size as _, 1, 0, bid as _)
-            .build()
-            .user_data(u64::MAX);
+        self.offset += offset;
+        self.len = len;
+    }
+}
 
-        let mut sq = driver.uring.submission();
+impl ops::Deref for ProvidedBuf {
+    type Target = [u8];
 
-        if unsafe { sq.push(&op) }.is_err() {
-            unimplemented!();
+    fn deref(&self) -> &[u8] {
+        match self.bid {
+            Some(bid) => self.pool.buf(bid, self.offset, self.len),
+            None => &[],
         }
     }
 }
+
+impl Drop for ProvidedBuf {
+    fn drop(&mut self) {
+        if let Some(bid) = self.bid {
+            self.pool.provide(bid);
+        }
+    }
+}
+
+impl fmt::Debug for BufPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufPool")
+            .field("buf_group", &self.inner.bgid)
+            .field("len", &self.inner.num)
+            .field("buf_size", &self.inner.size)
+            .finish()
+    }
+}
+
+impl fmt::Debug for ProvidedBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProvidedBuf")
+            .field("buf_id", &self.bid)
+            .field("len", &self.len)
+            .finish()
+    }
+}
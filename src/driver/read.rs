@@ -6,6 +6,7 @@ use crate::{
 use std::{
     io,
     task::{Context, Poll},
+    time::Duration,
 };
 
 pub(crate) struct Read<T> {
@@ -20,20 +21,56 @@ pub(crate) struct Read<T> {
 
 impl<T: IoBufMut> Op<Read<T>> {
     pub(crate) fn read_at(fd: &SharedFd, buf: T, offset: u64) -> io::Result<Op<Read<T>>> {
-        use io_uring::{opcode, types};
+        Op::read_at_with_deadline(fd, buf, offset, None)
+    }
 
-        Op::submit_with(
+    /// Submit a read which is cancelled by the kernel if it does not complete
+    /// within `deadline`.
+    pub(crate) fn read_at_with_deadline(
+        fd: &SharedFd,
+        buf: T,
+        offset: u64,
+        deadline: Option<Duration>,
+    ) -> io::Result<Op<Read<T>>> {
+        use io_uring::opcode;
+
+        Op::submit_with_deadline(
             Read {
                 fd: fd.clone(),
                 buf,
             },
+            deadline,
             |read| {
                 // Get raw buffer info
                 let ptr = read.buf.stable_mut_ptr();
                 let len = read.buf.bytes_total();
-                opcode::Read::new(types::Fd(fd.raw_fd()), ptr, len as _)
+                with_target!(fd, |fd| opcode::Read::new(fd, ptr, len as _)
                     .offset(offset as _)
-                    .build()
+                    .build())
+            },
+        )
+    }
+
+    /// Submit a `recv` with `flags` (`MSG_*`).
+    pub(crate) fn recv_with_flags(
+        fd: &SharedFd,
+        buf: T,
+        flags: libc::c_int,
+    ) -> io::Result<Op<Read<T>>> {
+        use io_uring::opcode;
+
+        Op::submit_with(
+            Read {
+                fd: fd.clone(),
+                buf,
+            },
+            |read| {
+                // Get raw buffer info
+                let ptr = read.buf.stable_mut_ptr();
+                let len = read.buf.bytes_total();
+                with_target!(fd, |fd| opcode::Recv::new(fd, ptr, len as _)
+                    .flags(flags)
+                    .build())
             },
         )
     }
@@ -0,0 +1,63 @@
+use crate::{
+    buf::{fixed::FixedBuf, IoBuf, IoBufMut},
+    driver::{Op, SharedFd},
+    BufResult,
+};
+use std::io;
+
+pub(crate) struct ReadFixed {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    /// Reference to the in-flight buffer.
+    pub(crate) buf: FixedBuf,
+}
+
+impl Op<ReadFixed> {
+    pub(crate) fn read_fixed_at(
+        fd: &SharedFd,
+        buf: FixedBuf,
+        offset: u64,
+    ) -> io::Result<Op<ReadFixed>> {
+        use io_uring::opcode;
+
+        Op::submit_with(
+            ReadFixed {
+                fd: fd.clone(),
+                buf,
+            },
+            |read| {
+                // Get raw buffer info
+                let ptr = read.buf.stable_mut_ptr();
+                let len = read.buf.bytes_total();
+                let buf_index = read.buf.buf_index();
+                with_target!(fd, |fd| opcode::ReadFixed::new(
+                    fd, ptr, len as _, buf_index
+                )
+                .offset(offset as _)
+                .build())
+            },
+        )
+    }
+
+    pub(crate) async fn read(self) -> BufResult<usize, FixedBuf> {
+        let complete = self.await;
+
+        // Convert the operation result to `usize`
+        let res = complete.result.map(|v| v as usize);
+        // Recover the buffer
+        let mut buf = complete.data.buf;
+
+        // If the operation was successful, advance the initialized cursor.
+        if let Ok(n) = res {
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe {
+                buf.set_init(n);
+            }
+        }
+
+        (res, buf)
+    }
+}
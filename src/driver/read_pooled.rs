@@ -0,0 +1,79 @@
+use crate::driver::{
+    pool::{BufPool, ProvidedBuf},
+    Op, SharedFd,
+};
+use io_uring::{cqueue, opcode, squeue};
+use std::{io, ptr};
+
+pub(crate) struct ReadPooled {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    /// Pool the kernel selects the buffer from.
+    pool: BufPool,
+}
+
+impl Op<ReadPooled> {
+    /// Submit a read into a buffer of `pool`.
+    pub(crate) fn read_pooled(fd: &SharedFd, pool: &BufPool) -> io::Result<Op<ReadPooled>> {
+        Op::submit_pooled(fd, pool, |read| {
+            with_target!(read.fd, |fd| opcode::Read::new(
+                fd,
+                ptr::null_mut(),
+                read.pool.buf_size() as _
+            )
+            .buf_group(read.pool.buf_group())
+            .build())
+        })
+    }
+
+    /// Submit a `recv` into a buffer of `pool`.
+    pub(crate) fn recv_pooled(fd: &SharedFd, pool: &BufPool) -> io::Result<Op<ReadPooled>> {
+        Op::submit_pooled(fd, pool, |recv| {
+            with_target!(recv.fd, |fd| opcode::Recv::new(
+                fd,
+                ptr::null_mut(),
+                recv.pool.buf_size() as _
+            )
+            .buf_group(recv.pool.buf_group())
+            .build())
+        })
+    }
+
+    fn submit_pooled<F>(fd: &SharedFd, pool: &BufPool, f: F) -> io::Result<Op<ReadPooled>>
+    where
+        F: FnOnce(&mut ReadPooled) -> squeue::Entry,
+    {
+        let op = Op::submit_with(
+            ReadPooled {
+                fd: fd.clone(),
+                pool: pool.clone(),
+            },
+            |read| f(read).flags(squeue::Flags::BUFFER_SELECT),
+        )?;
+
+        // A buffer selected for a dropped read must go back to the pool.
+        Ok(op.on_orphaned(|read, _, flags| {
+            if let Some(bid) = cqueue::buffer_select(flags) {
+                read.pool.pool().provide(bid);
+            }
+        }))
+    }
+
+    /// Returns `None` if the kernel did not select a buffer, which may happen
+    /// for empty reads.
+    pub(crate) async fn read(self) -> io::Result<Option<ProvidedBuf>> {
+        let complete = self.await;
+        let pool = complete.data.pool.pool();
+
+        // Take ownership of the selected buffer first, so that it is given
+        // back on error.
+        let buf = cqueue::buffer_select(complete.flags)
+            .map(|bid| pool.take(bid, *complete.result.as_ref().unwrap_or(&0) as usize));
+
+        complete.result?;
+        Ok(buf)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    buf::IoBufMut,
+    driver::{Op, SharedFd},
+    BufResult,
+};
+use std::io;
+
+pub(crate) struct Readv<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    /// Reference to the in-flight buffers.
+    pub(crate) bufs: Vec<T>,
+
+    /// One entry per buffer, read by the kernel when the operation is
+    /// prepared.
+    iovecs: Vec<libc::iovec>,
+}
+
+impl<T: IoBufMut> Op<Readv<T>> {
+    pub(crate) fn readv_at(
+        fd: &SharedFd,
+        mut bufs: Vec<T>,
+        offset: u64,
+    ) -> io::Result<Op<Readv<T>>> {
+        use io_uring::opcode;
+
+        let iovecs = bufs
+            .iter_mut()
+            .map(|buf| libc::iovec {
+                iov_base: buf.stable_mut_ptr() as *mut _,
+                iov_len: buf.bytes_total(),
+            })
+            .collect();
+
+        Op::submit_with(
+            Readv {
+                fd: fd.clone(),
+                bufs,
+                iovecs,
+            },
+            |read| {
+                with_target!(fd, |fd| opcode::Readv::new(
+                    fd,
+                    read.iovecs.as_ptr(),
+                    read.iovecs.len() as _
+                )
+                .offset(offset as _)
+                .build())
+            },
+        )
+    }
+
+    pub(crate) async fn read(self) -> BufResult<usize, Vec<T>> {
+        let complete = self.await;
+
+        // Convert the operation result to `usize`
+        let res = complete.result.map(|v| v as usize);
+        // Recover the buffers
+        let mut bufs = complete.data.bufs;
+
+        // If the operation was successful, advance the initialized cursors.
+        // The kernel fills the buffers in order.
+        if let Ok(mut n) = res {
+            for buf in &mut bufs {
+                let len = n.min(buf.bytes_total());
+
+                // Safety: the kernel wrote `len` bytes to the buffer.
+                unsafe {
+                    buf.set_init(len);
+                }
+
+                n -= len;
+            }
+        }
+
+        (res, bufs)
+    }
+}
@@ -19,12 +19,32 @@ pub(crate) struct RecvFrom<T> {
     pub(crate) buf: T,
     io_slices: Vec<IoSliceMut<'static>>,
     pub(crate) socket_addr: Box<SockAddr>,
+    /// Receives control messages up to its capacity
+    pub(crate) control: Vec<u8>,
     pub(crate) msghdr: Box<libc::msghdr>,
 }
 
+/// Outcome of a `recvmsg`.
+pub(crate) struct RecvMeta {
+    pub(crate) len: usize,
+    pub(crate) socket_addr: Option<SocketAddr>,
+    /// Flags set by the kernel, e.g. `MSG_CTRUNC`
+    pub(crate) flags: libc::c_int,
+}
+
 impl<T: IoBufMut> Op<RecvFrom<T>> {
-    pub(crate) fn recv_from(fd: &SharedFd, mut buf: T) -> io::Result<Op<RecvFrom<T>>> {
-        use io_uring::{opcode, types};
+    pub(crate) fn recv_from(fd: &SharedFd, buf: T) -> io::Result<Op<RecvFrom<T>>> {
+        Op::recv_msg(fd, buf, Vec::new())
+    }
+
+    /// Submit a `recvmsg` receiving control messages into the spare capacity
+    /// of `control`.
+    pub(crate) fn recv_msg(
+        fd: &SharedFd,
+        mut buf: T,
+        mut control: Vec<u8>,
+    ) -> io::Result<Op<RecvFrom<T>>> {
+        use io_uring::opcode;
 
         let mut io_slices = vec![IoSliceMut::new(unsafe {
             std::slice::from_raw_parts_mut(buf.stable_mut_ptr(), buf.bytes_total())
@@ -38,32 +58,70 @@ impl<T: IoBufMut> Op<RecvFrom<T>> {
         msghdr.msg_name = socket_addr.as_ptr() as *mut libc::c_void;
         msghdr.msg_namelen = socket_addr.len();
 
+        // Received descriptors are not inherited by child processes.
+        let mut flags = 0;
+        control.clear();
+        if control.capacity() > 0 {
+            msghdr.msg_control = control.as_mut_ptr().cast();
+            msghdr.msg_controllen = control.capacity() as _;
+            flags |= libc::MSG_CMSG_CLOEXEC as u32;
+        }
+
         Op::submit_with(
             RecvFrom {
                 fd: fd.clone(),
                 buf,
                 io_slices,
                 socket_addr,
+                control,
                 msghdr,
             },
             |recv_from| {
-                opcode::RecvMsg::new(
-                    types::Fd(recv_from.fd.raw_fd()),
+                with_target!(recv_from.fd, |fd| opcode::RecvMsg::new(
+                    fd,
                     recv_from.msghdr.as_mut() as *mut _,
                 )
-                .build()
+                .flags(flags)
+                .build())
             },
         )
     }
 
-    pub(crate) async fn recv(mut self) -> BufResult<(usize, SocketAddr), T> {
+    /// Returns the buffer and control messages along with the result.
+    pub(crate) async fn recv_with_control(self) -> BufResult<RecvMeta, (T, Vec<u8>)> {
+        let complete = self.await;
+        let data = complete.data;
+        let mut buf = data.buf;
+        let mut control = data.control;
+
+        let result = complete.result.map(|v| {
+            let len = v as usize;
+
+            // Safety: the kernel wrote `len` bytes to the buffer, and
+            // `msg_controllen` bytes of control messages.
+            unsafe {
+                buf.set_init(len);
+                control.set_len(data.msghdr.msg_controllen as usize);
+            }
+
+            RecvMeta {
+                len,
+                socket_addr: data.socket_addr.as_socket(),
+                flags: data.msghdr.msg_flags,
+            }
+        });
+
+        (result, (buf, control))
+    }
+
+    pub(crate) async fn recv(mut self) -> BufResult<(usize, SockAddr), T> {
         poll_fn(move |cx| self.poll_recv_from(cx)).await
     }
 
     pub(crate) fn poll_recv_from(
         &mut self,
         cx: &mut Context<'_>,
-    ) -> Poll<BufResult<(usize, SocketAddr), T>> {
+    ) -> Poll<BufResult<(usize, SockAddr), T>> {
         use std::{future::Future, pin::Pin};
 
         let complete = ready!(Pin::new(self).poll(cx));
@@ -74,16 +132,40 @@ impl<T: IoBufMut> Op<RecvFrom<T>> {
         let result = match complete.result {
             Ok(v) => {
                 let v = v as usize;
-                let socket_addr: Option<SocketAddr> = (*complete.data.socket_addr).as_socket();
                 // If the operation was successful, advance the initialized cursor.
                 // Safety: the kernel wrote `v` bytes to the buffer.
                 unsafe {
                     buf.set_init(v);
                 }
-                Ok((v, socket_addr.unwrap()))
+                Ok((
+                    v,
+                    received_addr(&complete.data.socket_addr, &complete.data.msghdr),
+                ))
             }
             Err(e) => Err(e),
         };
         Poll::Ready((result, buf))
     }
 }
+
+/// Returns the source address, with the length set by the kernel. Unix
+/// socket addresses are only meaningful up to that length.
+fn received_addr(socket_addr: &SockAddr, msghdr: &libc::msghdr) -> SockAddr {
+    let len = msghdr.msg_namelen;
+
+    // Safety: the storage of the received address is copied as a whole, and
+    // the kernel never sets a length larger than it.
+    unsafe {
+        SockAddr::init(|storage, storage_len| {
+            std::ptr::copy_nonoverlapping(
+                socket_addr.as_ptr().cast::<u8>(),
+                storage.cast::<u8>(),
+                std::mem::size_of::<libc::sockaddr_storage>(),
+            );
+            *storage_len = len;
+            Ok(())
+        })
+        .unwrap()
+        .1
+    }
+}
@@ -0,0 +1,236 @@
+use crate::driver::{
+    pool::{BufPool, ProvidedBuf},
+    MultiOp, Op, SharedFd,
+};
+use futures_lite::{ready, StreamExt};
+use io_uring::{cqueue, opcode, types::RecvMsgOut};
+use socket2::SockAddr;
+use std::{
+    io,
+    net::SocketAddr,
+    task::{Context, Poll},
+};
+
+pub(crate) struct RecvMulti {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    /// Pool the kernel selects the buffers from.
+    pool: BufPool,
+
+    /// Set for `recvmsg`, see `msghdr`.
+    msghdr: Option<Box<libc::msghdr>>,
+}
+
+impl Op<RecvMulti> {
+    /// Submit a multishot `recv`, completing once for every buffer of `pool`
+    /// the kernel fills.
+    pub(crate) fn recv_multi(fd: &SharedFd, pool: &BufPool) -> io::Result<MultiOp<RecvMulti>> {
+        Op::submit_multi(fd, pool, None, |recv| {
+            with_target!(recv.fd, |fd| opcode::RecvMulti::new(
+                fd,
+                recv.pool.buf_group()
+            )
+            .build())
+        })
+    }
+
+    /// Submit a multishot `recvmsg`, completing once for every datagram. Each
+    /// buffer starts with an `io_uring_recvmsg_out` header and the source
+    /// address.
+    pub(crate) fn recv_msg_multi(fd: &SharedFd, pool: &BufPool) -> io::Result<MultiOp<RecvMulti>> {
+        Op::submit_multi(fd, pool, Some(Box::new(msghdr())), |recv| {
+            let msghdr = recv.msghdr.as_deref().unwrap();
+
+            with_target!(recv.fd, |fd| opcode::RecvMsgMulti::new(
+                fd,
+                msghdr as *const _,
+                recv.pool.buf_group()
+            )
+            .build())
+        })
+    }
+
+    fn submit_multi<F>(
+        fd: &SharedFd,
+        pool: &BufPool,
+        msghdr: Option<Box<libc::msghdr>>,
+        f: F,
+    ) -> io::Result<MultiOp<RecvMulti>>
+    where
+        F: FnOnce(&mut RecvMulti) -> io_uring::squeue::Entry,
+    {
+        let op = Op::submit_multi_with(
+            RecvMulti {
+                fd: fd.clone(),
+                pool: pool.clone(),
+                msghdr,
+            },
+            f,
+        )?;
+
+        // Buffers selected after the stream was dropped go back to the pool.
+        Ok(op.on_orphaned(|recv, _, flags| {
+            if let Some(bid) = cqueue::buffer_select(flags) {
+                recv.pool.pool().provide(bid);
+            }
+        }))
+    }
+}
+
+/// A filled buffer, with the source address for datagrams.
+pub(crate) type Received = (ProvidedBuf, Option<SocketAddr>);
+
+/// Buffers filled by a multishot `recv` or `recvmsg`.
+///
+/// The operation is submitted on the first poll, and submitted again whenever
+/// the kernel stops it without an error, or because the pool ran out of
+/// buffers. Any other error, the end of the stream, or a cancellation ends it.
+pub(crate) struct RecvStream {
+    fd: SharedFd,
+    pool: BufPool,
+    msg: bool,
+    op: Option<MultiOp<RecvMulti>>,
+    cancelled: bool,
+    done: bool,
+}
+
+impl RecvStream {
+    /// With `msg`, datagrams are received along with their source address.
+    pub(crate) fn new(fd: &SharedFd, pool: &BufPool, msg: bool) -> RecvStream {
+        RecvStream {
+            fd: fd.clone(),
+            pool: pool.clone(),
+            msg,
+            op: None,
+            cancelled: false,
+            done: false,
+        }
+    }
+
+    /// Stops receiving. Buffers already filled are still yielded, then the
+    /// stream ends.
+    pub(crate) fn cancel(&mut self) {
+        self.cancelled = true;
+
+        match &mut self.op {
+            Some(op) => op.cancel(),
+            None => self.done = true,
+        }
+    }
+
+    pub(crate) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<io::Result<Received>>> {
+        loop {
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            let op = match &mut self.op {
+                Some(op) => op,
+                None => {
+                    let op = if self.msg {
+                        Op::recv_msg_multi(&self.fd, &self.pool)
+                    } else {
+                        Op::recv_multi(&self.fd, &self.pool)
+                    };
+
+                    match op {
+                        Ok(op) => self.op.insert(op),
+                        Err(e) => return Poll::Ready(Some(Err(e))),
+                    }
+                }
+            };
+
+            let cqe = match ready!(op.poll_next(cx)) {
+                Some(cqe) => cqe,
+                None => {
+                    self.op = None;
+                    self.done = self.cancelled;
+                    continue;
+                }
+            };
+
+            // Take ownership of the selected buffer first, so that it is given
+            // back on error.
+            let pool = self.pool.pool();
+            let buf = cqueue::buffer_select(cqe.flags)
+                .map(|bid| pool.take(bid, *cqe.result.as_ref().unwrap_or(&0) as usize));
+
+            match cqe.result {
+                Ok(_) => {}
+                Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => {
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Err(e) => {
+                    self.finish();
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+
+            let mut buf = match buf {
+                // An empty read, the peer closed the stream.
+                None => {
+                    self.finish();
+                    return Poll::Ready(None);
+                }
+                Some(buf) if buf.is_empty() => {
+                    self.finish();
+                    return Poll::Ready(None);
+                }
+                Some(buf) => buf,
+            };
+
+            if !self.msg {
+                return Poll::Ready(Some(Ok((buf, None))));
+            }
+
+            let (offset, len, addr) = match RecvMsgOut::parse(&buf, &msghdr()) {
+                Ok(out) => (
+                    out.payload_data().as_ptr() as usize - buf.as_ptr() as usize,
+                    out.payload_data().len(),
+                    socket_addr(out.name_data()),
+                ),
+                Err(()) => {
+                    return Poll::Ready(Some(Err(io::Error::other("invalid recvmsg completion"))))
+                }
+            };
+
+            buf.narrow(offset, len);
+            return Poll::Ready(Some(Ok((buf, addr))));
+        }
+    }
+
+    fn finish(&mut self) {
+        self.done = true;
+        self.op = None;
+    }
+}
+
+/// Header of multishot `recvmsg` operations. The kernel only reads the name
+/// and control lengths, which are needed again to parse the buffers.
+///
+/// Room is reserved for IPv6 source addresses, and no control data.
+fn msghdr() -> libc::msghdr {
+    let mut msghdr: libc::msghdr = unsafe { std::mem::zeroed() };
+    msghdr.msg_namelen = std::mem::size_of::<libc::sockaddr_in6>() as _;
+    msghdr
+}
+
+/// Parses a socket address written by the kernel.
+fn socket_addr(name: &[u8]) -> Option<SocketAddr> {
+    let (_, addr) = unsafe {
+        SockAddr::init(|storage, len| {
+            let name_len = name
+                .len()
+                .min(std::mem::size_of::<libc::sockaddr_storage>());
+            std::ptr::copy_nonoverlapping(name.as_ptr(), storage as *mut u8, name_len);
+            *len = name_len as _;
+            Ok(())
+        })
+        .ok()?
+    };
+
+    addr.as_socket()
+}
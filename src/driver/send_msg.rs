@@ -0,0 +1,81 @@
+use crate::{
+    buf::IoBuf,
+    driver::{writev::iovecs, Op, SharedFd},
+    BufResult,
+};
+use socket2::SockAddr;
+use std::{boxed::Box, io, net::SocketAddr};
+
+pub(crate) struct SendMsg<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    pub(crate) bufs: Vec<T>,
+
+    // Referenced by the header, and read by the kernel when the operation is
+    // prepared
+    #[allow(dead_code)]
+    iovecs: Vec<libc::iovec>,
+    #[allow(dead_code)]
+    socket_addr: Option<Box<SockAddr>>,
+
+    /// Encoded control messages
+    control: Vec<u8>,
+
+    msghdr: Box<libc::msghdr>,
+}
+
+impl<T: IoBuf> Op<SendMsg<T>> {
+    /// Submit a `sendmsg` of all `bufs`, to `socket_addr` if set, along with
+    /// the encoded control messages in `control`.
+    pub(crate) fn send_msg(
+        fd: &SharedFd,
+        bufs: Vec<T>,
+        socket_addr: Option<SocketAddr>,
+        control: Vec<u8>,
+    ) -> io::Result<Op<SendMsg<T>>> {
+        use io_uring::opcode;
+
+        let iovecs = iovecs(&bufs);
+        let socket_addr = socket_addr.map(|addr| Box::new(SockAddr::from(addr)));
+
+        let mut msghdr: Box<libc::msghdr> = Box::new(unsafe { std::mem::zeroed() });
+        msghdr.msg_iov = iovecs.as_ptr() as *mut _;
+        msghdr.msg_iovlen = iovecs.len() as _;
+        if let Some(socket_addr) = &socket_addr {
+            msghdr.msg_name = socket_addr.as_ptr() as *mut libc::c_void;
+            msghdr.msg_namelen = socket_addr.len();
+        }
+        if !control.is_empty() {
+            msghdr.msg_control = control.as_ptr() as *mut _;
+            msghdr.msg_controllen = control.len() as _;
+        }
+
+        Op::submit_with(
+            SendMsg {
+                fd: fd.clone(),
+                bufs,
+                iovecs,
+                socket_addr,
+                control,
+                msghdr,
+            },
+            |send| {
+                with_target!(send.fd, |fd| opcode::SendMsg::new(
+                    fd,
+                    send.msghdr.as_ref() as *const _
+                )
+                .build())
+            },
+        )
+    }
+
+    /// Returns the buffers and control messages along with the result.
+    pub(crate) async fn send(self) -> BufResult<usize, (Vec<T>, Vec<u8>)> {
+        let complete = self.await;
+        let data = complete.data;
+        (complete.result.map(|v| v as _), (data.bufs, data.control))
+    }
+}
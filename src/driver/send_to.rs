@@ -9,7 +9,6 @@ use std::{
     boxed::Box,
     io,
     io::IoSlice,
-    net::SocketAddr,
     task::{Context, Poll},
 };
 
@@ -28,15 +27,15 @@ impl<T: IoBuf> Op<SendTo<T>> {
     pub(crate) fn send_to(
         fd: &SharedFd,
         buf: T,
-        socket_addr: SocketAddr,
+        socket_addr: SockAddr,
     ) -> io::Result<Op<SendTo<T>>> {
-        use io_uring::{opcode, types};
+        use io_uring::opcode;
 
         let io_slices = vec![IoSlice::new(unsafe {
             std::slice::from_raw_parts(buf.stable_ptr(), buf.bytes_init())
         })];
 
-        let socket_addr = Box::new(SockAddr::from(socket_addr));
+        let socket_addr = Box::new(socket_addr);
 
         let mut msghdr: Box<libc::msghdr> = Box::new(unsafe { std::mem::zeroed() });
         msghdr.msg_iov = io_slices.as_ptr() as *mut _;
@@ -53,11 +52,11 @@ impl<T: IoBuf> Op<SendTo<T>> {
                 msghdr,
             },
             |send_to| {
-                opcode::SendMsg::new(
-                    types::Fd(send_to.fd.raw_fd()),
+                with_target!(send_to.fd, |fd| opcode::SendMsg::new(
+                    fd,
                     send_to.msghdr.as_ref() as *const _,
                 )
-                .build()
+                .build())
             },
         )
     }
@@ -0,0 +1,114 @@
+use crate::{
+    buf::IoBuf,
+    driver::{MultiOp, Op, SharedFd},
+    BufResult,
+};
+use futures_lite::StreamExt;
+use socket2::SockAddr;
+use std::{boxed::Box, io, io::IoSlice, net::SocketAddr};
+
+pub(crate) struct SendZc<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    pub(crate) buf: T,
+
+    /// Destination of `sendmsg`, read by the kernel when the operation is
+    /// prepared.
+    #[allow(dead_code)]
+    msg: Option<Box<SendMsg>>,
+}
+
+struct SendMsg {
+    io_slices: [IoSlice<'static>; 1],
+    socket_addr: SockAddr,
+    msghdr: libc::msghdr,
+}
+
+impl<T: IoBuf> Op<SendZc<T>> {
+    /// Submit a zero-copy `send`. With `buf_index`, `buf` is a buffer
+    /// registered with the kernel at that index.
+    pub(crate) fn send_zc(
+        fd: &SharedFd,
+        buf: T,
+        buf_index: Option<u16>,
+    ) -> io::Result<MultiOp<SendZc<T>>> {
+        use io_uring::opcode;
+
+        Op::submit_multi_with(
+            SendZc {
+                fd: fd.clone(),
+                buf,
+                msg: None,
+            },
+            |send| {
+                // Get raw buffer info
+                let ptr = send.buf.stable_ptr();
+                let len = send.buf.bytes_init();
+
+                with_target!(send.fd, |fd| opcode::SendZc::new(fd, ptr, len as _)
+                    .buf_index(buf_index)
+                    .build())
+            },
+        )
+    }
+
+    /// Submit a zero-copy `sendmsg` to `socket_addr`.
+    pub(crate) fn send_to_zc(
+        fd: &SharedFd,
+        buf: T,
+        socket_addr: SocketAddr,
+    ) -> io::Result<MultiOp<SendZc<T>>> {
+        use io_uring::opcode;
+
+        let io_slices = [IoSlice::new(unsafe {
+            std::slice::from_raw_parts(buf.stable_ptr(), buf.bytes_init())
+        })];
+
+        let mut msg = Box::new(SendMsg {
+            io_slices,
+            socket_addr: SockAddr::from(socket_addr),
+            msghdr: unsafe { std::mem::zeroed() },
+        });
+        msg.msghdr.msg_iov = msg.io_slices.as_ptr() as *mut _;
+        msg.msghdr.msg_iovlen = msg.io_slices.len() as _;
+        msg.msghdr.msg_name = msg.socket_addr.as_ptr() as *mut libc::c_void;
+        msg.msghdr.msg_namelen = msg.socket_addr.len();
+
+        Op::submit_multi_with(
+            SendZc {
+                fd: fd.clone(),
+                buf,
+                msg: Some(msg),
+            },
+            |send| {
+                let msghdr = &send.msg.as_ref().unwrap().msghdr;
+
+                with_target!(send.fd, |fd| opcode::SendMsgZc::new(fd, msghdr as *const _)
+                    .build())
+            },
+        )
+    }
+}
+
+impl<T: IoBuf> MultiOp<SendZc<T>> {
+    /// Returns the result of the send once the kernel no longer uses the
+    /// buffer.
+    ///
+    /// The first completion carries the result. When data was sent, it is
+    /// flagged with `IORING_CQE_F_MORE`, and a notification follows once the
+    /// buffer is released.
+    pub(crate) async fn send(mut self) -> BufResult<usize, T> {
+        let result = match self.next().await {
+            Some(cqe) => cqe.result.map(|v| v as _),
+            None => unreachable!("operation completed without a result"),
+        };
+
+        // Wait for the notification.
+        while self.next().await.is_some() {}
+
+        (result, self.into_data().buf)
+    }
+}
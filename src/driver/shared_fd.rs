@@ -1,9 +1,12 @@
 use crate::driver::{Close, Op};
 use std::{
     cell::RefCell,
-    os::unix::io::{FromRawFd, RawFd},
+    future::Future,
+    io,
+    os::unix::io::RawFd,
+    pin::Pin,
     rc::Rc,
-    task::Waker,
+    task::{Context, Poll, Waker},
 };
 
 // Tracks in-flight operations on a file descriptor. Ensures all in-flight
@@ -15,12 +18,20 @@ pub(crate) struct SharedFd {
 
 struct Inner {
     // Open file descriptor
-    fd: RawFd,
+    fd: Fd,
 
     // Waker to notify when the close operation completes.
     state: RefCell<State>,
 }
 
+/// A descriptor, either a regular one or a slot in the fixed-file table of the
+/// driver.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Fd {
+    Raw(RawFd),
+    Fixed(u32),
+}
+
 enum State {
     /// Initial state
     Init,
@@ -37,6 +48,15 @@ enum State {
 
 impl SharedFd {
     pub(crate) fn new(fd: RawFd) -> SharedFd {
+        SharedFd::from_fd(Fd::Raw(fd))
+    }
+
+    /// Wraps a slot of the fixed-file table.
+    pub(crate) fn new_fixed(index: u32) -> SharedFd {
+        SharedFd::from_fd(Fd::Fixed(index))
+    }
+
+    fn from_fd(fd: Fd) -> SharedFd {
         SharedFd {
             inner: Rc::new(Inner {
                 fd,
@@ -45,37 +65,61 @@ impl SharedFd {
         }
     }
 
-    /// Returns the RawFd
+    /// Returns the RawFd, or `-1` if the descriptor only exists in the
+    /// fixed-file table. System calls on `-1` fail with `EBADF`.
     pub(crate) fn raw_fd(&self) -> RawFd {
-        self.inner.fd
+        match self.inner.fd {
+            Fd::Raw(fd) => fd,
+            Fd::Fixed(_) => -1,
+        }
+    }
+
+    /// Returns the slot in the fixed-file table, if the descriptor is fixed.
+    pub(crate) fn fixed(&self) -> Option<u32> {
+        match self.inner.fd {
+            Fd::Raw(_) => None,
+            Fd::Fixed(index) => Some(index),
+        }
     }
 
     /// An FD cannot be closed until all in-flight operation have completed.
     /// This prevents bugs where in-flight reads could operate on the incorrect
     /// file descriptor.
     ///
-    /// TO model this, if there are no in-flight operations, then
-    pub(crate) async fn close(mut self) {
-        // Get a mutable reference to Inner, indicating there are no
-        // in-flight operations on the FD.
-        if let Some(inner) = Rc::get_mut(&mut self.inner) {
-            // Submit the close operation
-            inner.submit_close_op();
-        }
+    /// To model this, the close is submitted once this is the last reference
+    /// to the FD. Until then, the dropping of other references wakes the
+    /// closer, see the `Drop` implementation.
+    pub(crate) async fn close(mut self) -> io::Result<()> {
+        futures_lite::future::poll_fn(|cx| {
+            // Get a mutable reference to Inner, indicating there are no
+            // in-flight operations on the FD.
+            if let Some(inner) = Rc::get_mut(&mut self.inner) {
+                if let State::Init | State::Waiting(_) = RefCell::get_mut(&mut inner.state) {
+                    // Submit the close operation
+                    inner.submit_close_op()?;
+                }
+            }
 
-        self.inner.closed().await;
+            self.inner.poll_closed(cx)
+        })
+        .await
     }
 }
 
 impl Inner {
     /// If there are no in-flight operations, submit the operation.
-    fn submit_close_op(&mut self) {
+    ///
+    /// Fails if the fallback synchronous close failed.
+    fn submit_close_op(&mut self) -> io::Result<()> {
         // Close the FD
         let state = RefCell::get_mut(&mut self.state);
 
         // Submit a close operation
-        *state = match Op::close(self.fd) {
-            Ok(op) => State::Closing(op),
+        match Op::close(self.fd) {
+            Ok(op) => {
+                *state = State::Closing(op);
+                Ok(())
+            }
             Err(_) => {
                 // Submitting the operation failed, we fall back on a
                 // synchronous `close`. This is safe as, at this point, we
@@ -83,50 +127,61 @@ impl Inner {
                 // common cause for an error is attempting to close the FD while
                 // off runtime.
                 //
-                // This is done by initializing a `File` with the FD and
-                // dropping it.
+                // Fixed slots cannot be closed without the driver, they are
+                // released with the ring.
                 //
                 // TODO: Should we warn?
-                let _ = unsafe { std::fs::File::from_raw_fd(self.fd) };
+                *state = State::Closed;
 
-                State::Closed
+                match self.fd {
+                    Fd::Raw(fd) => syscall!(close(fd)).map(drop),
+                    Fd::Fixed(_) => Ok(()),
+                }
             }
-        };
+        }
     }
 
-    /// Completes when the FD has been closed.
-    async fn closed(&self) {
-        use std::{future::Future, pin::Pin, task::Poll};
-
-        futures_lite::future::poll_fn(|cx| {
-            let mut state = self.state.borrow_mut();
+    /// Completes when the FD has been closed, with the result of the close
+    /// operation.
+    fn poll_closed(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut state = self.state.borrow_mut();
 
-            match &mut *state {
-                State::Init => {
-                    *state = State::Waiting(Some(cx.waker().clone()));
-                    Poll::Pending
+        match &mut *state {
+            State::Init | State::Waiting(None) => {
+                *state = State::Waiting(Some(cx.waker().clone()));
+                Poll::Pending
+            }
+            State::Waiting(Some(waker)) => {
+                if !waker.will_wake(cx.waker()) {
+                    *waker = cx.waker().clone();
                 }
-                State::Waiting(Some(waker)) => {
-                    if !waker.will_wake(cx.waker()) {
-                        *waker = cx.waker().clone();
-                    }
 
-                    Poll::Pending
-                }
-                State::Waiting(None) => {
-                    *state = State::Waiting(Some(cx.waker().clone()));
-                    Poll::Pending
-                }
-                State::Closing(op) => {
-                    // Nothing to do if the close opeation failed.
-                    let _ = ready!(Pin::new(op).poll(cx));
-                    *state = State::Closed;
-                    Poll::Ready(())
+                Poll::Pending
+            }
+            State::Closing(op) => {
+                let completion = ready!(Pin::new(op).poll(cx));
+                *state = State::Closed;
+                Poll::Ready(completion.result.map(drop))
+            }
+            State::Closed => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+impl Drop for SharedFd {
+    fn drop(&mut self) {
+        // Once only the closer holds a reference, wake it to submit the close.
+        if Rc::strong_count(&self.inner) != 2 {
+            return;
+        }
+
+        if let Ok(mut state) = self.inner.state.try_borrow_mut() {
+            if let State::Waiting(waker) = &mut *state {
+                if let Some(waker) = waker.take() {
+                    waker.wake();
                 }
-                State::Closed => Poll::Ready(()),
             }
-        })
-        .await;
+        }
     }
 }
 
@@ -135,7 +190,7 @@ impl Drop for Inner {
         // Submit the close operation, if needed
         match RefCell::get_mut(&mut self.state) {
             State::Init | State::Waiting(..) => {
-                self.submit_close_op();
+                let _ = self.submit_close_op();
             }
             _ => {}
         }
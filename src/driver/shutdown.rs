@@ -0,0 +1,24 @@
+use crate::driver::{Op, SharedFd};
+use io_uring::opcode;
+use std::{io, net};
+
+pub(crate) struct Shutdown {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+}
+
+impl Op<Shutdown> {
+    pub(crate) fn shutdown(fd: &SharedFd, how: net::Shutdown) -> io::Result<Op<Shutdown>> {
+        let how = match how {
+            net::Shutdown::Read => libc::SHUT_RD,
+            net::Shutdown::Write => libc::SHUT_WR,
+            net::Shutdown::Both => libc::SHUT_RDWR,
+        };
+
+        Op::submit_with(Shutdown { fd: fd.clone() }, |shutdown| {
+            with_target!(shutdown.fd, |fd| opcode::Shutdown::new(fd, how).build())
+        })
+    }
+}
@@ -1,12 +1,12 @@
 use crate::{
-    buf::{IoBuf, IoBufMut},
-    driver::{Op, SharedFd},
+    buf::{fixed::FixedBuf, IoBuf, IoBufMut},
+    driver::{BufPool, Incoming, Op, ProvidedBuf, RecvMeta, RecvStream, SharedFd},
 };
 use std::{
     io,
-    net::SocketAddr,
+    net::{SocketAddr, SocketAddrV6},
     os::unix::io::{AsRawFd, IntoRawFd, RawFd},
-    path::Path,
+    time::Duration,
 };
 
 #[derive(Clone)]
@@ -24,53 +24,193 @@ pub(crate) fn get_domain(socket_addr: SocketAddr) -> libc::c_int {
 
 impl Socket {
     pub(crate) fn new(socket_addr: SocketAddr, socket_type: libc::c_int) -> io::Result<Socket> {
-        let socket_type = socket_type | libc::SOCK_CLOEXEC;
-        let domain = get_domain(socket_addr);
-        let fd = socket2::Socket::new(domain.into(), socket_type.into(), None)?.into_raw_fd();
-        let fd = SharedFd::new(fd);
-        Ok(Socket { fd })
+        Socket::new_domain(get_domain(socket_addr), socket_type)
     }
 
     pub(crate) fn new_unix(socket_type: libc::c_int) -> io::Result<Socket> {
+        Socket::new_domain(libc::AF_UNIX, socket_type)
+    }
+
+    pub(crate) fn new_domain(domain: libc::c_int, socket_type: libc::c_int) -> io::Result<Socket> {
         let socket_type = socket_type | libc::SOCK_CLOEXEC;
-        let domain = libc::AF_UNIX;
         let fd = socket2::Socket::new(domain.into(), socket_type.into(), None)?.into_raw_fd();
         let fd = SharedFd::new(fd);
         Ok(Socket { fd })
     }
 
+    pub(crate) fn from_shared_fd(fd: SharedFd) -> Socket {
+        Socket { fd }
+    }
+
+    pub(crate) fn shared_fd(&self) -> &SharedFd {
+        &self.fd
+    }
+
     pub(crate) async fn write<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
         let op = Op::write_at(&self.fd, buf, 0).unwrap();
         op.write().await
     }
 
+    pub(crate) async fn send<T: IoBuf>(
+        &self,
+        buf: T,
+        flags: libc::c_int,
+    ) -> crate::BufResult<usize, T> {
+        let op = Op::send_with_flags(&self.fd, buf, flags).unwrap();
+        op.write().await
+    }
+
+    pub(crate) async fn writev<T: IoBuf>(&self, bufs: Vec<T>) -> crate::BufResult<usize, Vec<T>> {
+        let op = Op::writev_at(&self.fd, bufs, 0).unwrap();
+        op.write().await
+    }
+
+    pub(crate) async fn send_msg<T: IoBuf>(
+        &self,
+        bufs: Vec<T>,
+        socket_addr: Option<SocketAddr>,
+    ) -> crate::BufResult<usize, Vec<T>> {
+        let op = Op::send_msg(&self.fd, bufs, socket_addr, Vec::new()).unwrap();
+        let (res, (bufs, _)) = op.send().await;
+        (res, bufs)
+    }
+
+    pub(crate) async fn send_msg_with_control<T: IoBuf>(
+        &self,
+        bufs: Vec<T>,
+        socket_addr: Option<SocketAddr>,
+        control: Vec<u8>,
+    ) -> crate::BufResult<usize, (Vec<T>, Vec<u8>)> {
+        let op = Op::send_msg(&self.fd, bufs, socket_addr, control).unwrap();
+        op.send().await
+    }
+
+    pub(crate) async fn write_with_timeout<T: IoBuf>(
+        &self,
+        buf: T,
+        timeout: Duration,
+    ) -> crate::BufResult<usize, T> {
+        let op = Op::write_at_with_deadline(&self.fd, buf, 0, Some(timeout)).unwrap();
+        op.write().await
+    }
+
+    pub(crate) async fn write_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        let op = Op::write_fixed_at(&self.fd, buf, 0).unwrap();
+        op.write().await
+    }
+
     pub(crate) async fn send_to<T: IoBuf>(
         &self,
         buf: T,
-        socket_addr: SocketAddr,
+        socket_addr: socket2::SockAddr,
     ) -> crate::BufResult<usize, T> {
         let op = Op::send_to(&self.fd, buf, socket_addr).unwrap();
         op.send().await
     }
 
+    pub(crate) async fn send_zc<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
+        let op = Op::send_zc(&self.fd, buf, None).unwrap();
+        op.send().await
+    }
+
+    pub(crate) async fn send_zc_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        let buf_index = buf.buf_index();
+        let op = Op::send_zc(&self.fd, buf, Some(buf_index)).unwrap();
+        op.send().await
+    }
+
+    pub(crate) async fn send_to_zc<T: IoBuf>(
+        &self,
+        buf: T,
+        socket_addr: SocketAddr,
+    ) -> crate::BufResult<usize, T> {
+        let op = Op::send_to_zc(&self.fd, buf, socket_addr).unwrap();
+        op.send().await
+    }
+
     pub(crate) async fn read<T: IoBufMut>(&self, buf: T) -> crate::BufResult<usize, T> {
         let op = Op::read_at(&self.fd, buf, 0).unwrap();
         op.read().await
     }
 
+    pub(crate) async fn recv<T: IoBufMut>(
+        &self,
+        buf: T,
+        flags: libc::c_int,
+    ) -> crate::BufResult<usize, T> {
+        let op = Op::recv_with_flags(&self.fd, buf, flags).unwrap();
+        op.read().await
+    }
+
+    pub(crate) async fn read_with_timeout<T: IoBufMut>(
+        &self,
+        buf: T,
+        timeout: Duration,
+    ) -> crate::BufResult<usize, T> {
+        let op = Op::read_at_with_deadline(&self.fd, buf, 0, Some(timeout)).unwrap();
+        op.read().await
+    }
+
+    pub(crate) async fn read_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        let op = Op::read_fixed_at(&self.fd, buf, 0).unwrap();
+        op.read().await
+    }
+
+    pub(crate) async fn read_pooled(&self, pool: &BufPool) -> io::Result<Option<ProvidedBuf>> {
+        let op = Op::read_pooled(&self.fd, pool)?;
+
+        // An empty read is the end of the stream.
+        Ok(op.read().await?.filter(|buf| !buf.is_empty()))
+    }
+
+    pub(crate) async fn recv_pooled(&self, pool: &BufPool) -> io::Result<ProvidedBuf> {
+        let op = Op::recv_pooled(&self.fd, pool)?;
+
+        // The kernel selects no buffer for an empty datagram.
+        let buf = op.read().await?;
+        Ok(buf.unwrap_or_else(|| pool.pool().take_empty()))
+    }
+
+    /// Returns the buffers filled by a multishot `recv`, or with `msg` by a
+    /// multishot `recvmsg` along with the source addresses.
+    pub(crate) fn recv_multi(&self, pool: &BufPool, msg: bool) -> RecvStream {
+        RecvStream::new(&self.fd, pool, msg)
+    }
+
+    pub(crate) async fn recv_msg<T: IoBufMut>(
+        &self,
+        buf: T,
+        control: Vec<u8>,
+    ) -> crate::BufResult<RecvMeta, (T, Vec<u8>)> {
+        let op = Op::recv_msg(&self.fd, buf, control).unwrap();
+        op.recv_with_control().await
+    }
+
     pub(crate) async fn recv_from<T: IoBufMut>(
         &self,
         buf: T,
     ) -> crate::BufResult<(usize, SocketAddr), T> {
+        let (res, buf) = self.recv_from_unix(buf).await;
+        (res.and_then(|(n, addr)| Ok((n, inet_addr(addr)?))), buf)
+    }
+
+    pub(crate) async fn recv_from_unix<T: IoBufMut>(
+        &self,
+        buf: T,
+    ) -> crate::BufResult<(usize, socket2::SockAddr), T> {
         let op = Op::recv_from(&self.fd, buf).unwrap();
         op.recv().await
     }
 
-    pub(crate) async fn accept(&self) -> io::Result<(Socket, Option<SocketAddr>)> {
-        let op = Op::accept(&self.fd)?;
+    pub(crate) async fn accept(&self, fixed: bool) -> io::Result<(Socket, socket2::SockAddr)> {
+        let op = Op::accept(&self.fd, fixed)?;
         let completion = op.await;
         let fd = completion.result?;
-        let fd = SharedFd::new(fd as i32);
+        let fd = if fixed {
+            SharedFd::new_fixed(fd)
+        } else {
+            SharedFd::new(fd as i32)
+        };
         let data = completion.data;
         let socket = Socket { fd };
         let (_, addr) = unsafe {
@@ -80,7 +220,12 @@ impl Socket {
                 Ok(())
             })?
         };
-        Ok((socket, addr.as_socket()))
+        Ok((socket, addr))
+    }
+
+    /// Returns the connections accepted by a multishot accept.
+    pub(crate) fn incoming(&self) -> Incoming {
+        Incoming::new(&self.fd)
     }
 
     pub(crate) async fn connect(&self, socket_addr: socket2::SockAddr) -> io::Result<()> {
@@ -90,35 +235,79 @@ impl Socket {
         Ok(())
     }
 
+    pub(crate) async fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()> {
+        let op = Op::shutdown(&self.fd, how)?;
+        let completion = op.await;
+        completion.result?;
+        Ok(())
+    }
+
+    pub(crate) async fn close(self) -> io::Result<()> {
+        self.fd.close().await
+    }
+
     pub(crate) fn bind(socket_addr: SocketAddr, socket_type: libc::c_int) -> io::Result<Socket> {
         Self::bind_internal(
             socket_addr.into(),
             get_domain(socket_addr).into(),
             socket_type.into(),
+            None,
         )
     }
 
-    pub(crate) fn bind_unix<P: AsRef<Path>>(
-        path: P,
+    /// Binds an IPv6 socket, setting `IPV6_V6ONLY` first, as it cannot be
+    /// changed once bound.
+    pub(crate) fn bind_v6(
+        socket_addr: SocketAddrV6,
         socket_type: libc::c_int,
+        only_v6: bool,
     ) -> io::Result<Socket> {
-        let addr = socket2::SockAddr::unix(path.as_ref())?;
-        Self::bind_internal(addr, libc::AF_UNIX.into(), socket_type.into())
+        Self::bind_internal(
+            socket_addr.into(),
+            libc::AF_INET6.into(),
+            socket_type.into(),
+            Some(only_v6),
+        )
+    }
+
+    pub(crate) fn bind_unix(
+        addr: &socket2::SockAddr,
+        socket_type: libc::c_int,
+    ) -> io::Result<Socket> {
+        Self::bind_internal(addr.clone(), libc::AF_UNIX.into(), socket_type.into(), None)
+    }
+
+    /// Returns a pair of connected Unix sockets.
+    pub(crate) fn pair_unix(socket_type: libc::c_int) -> io::Result<(Socket, Socket)> {
+        let socket_type = socket_type | libc::SOCK_CLOEXEC;
+        let (a, b) = socket2::Socket::pair(libc::AF_UNIX.into(), socket_type.into(), None)?;
+        let a = Socket {
+            fd: SharedFd::new(a.into_raw_fd()),
+        };
+        let b = Socket {
+            fd: SharedFd::new(b.into_raw_fd()),
+        };
+        Ok((a, b))
     }
 
     fn bind_internal(
         socket_addr: socket2::SockAddr,
         domain: socket2::Domain,
         socket_type: socket2::Type,
+        only_v6: Option<bool>,
     ) -> io::Result<Socket> {
         let sys_listener = socket2::Socket::new(domain, socket_type, None)?;
 
-        sys_listener.set_reuse_port(true)?;
+        // `SO_REUSEPORT` is not supported on Unix domain sockets.
+        if domain != socket2::Domain::UNIX {
+            sys_listener.set_reuse_port(true)?;
+        }
         sys_listener.set_reuse_address(true)?;
 
-        // TODO: config for buffer sizes
-        // sys_listener.set_send_buffer_size(send_buf_size)?;
-        // sys_listener.set_recv_buffer_size(recv_buf_size)?;
+        // Otherwise, the `net.ipv6.bindv6only` default applies.
+        if let Some(only_v6) = only_v6 {
+            sys_listener.set_only_v6(only_v6)?;
+        }
 
         sys_listener.bind(&socket_addr)?;
 
@@ -127,10 +316,81 @@ impl Socket {
         Ok(Self { fd })
     }
 
+    /// Binds an unbound socket, leaving its options as they are.
+    pub(crate) fn bind_addr(&self, socket_addr: SocketAddr) -> io::Result<()> {
+        self.sock_ref()?.bind(&socket_addr.into())
+    }
+
     pub(crate) fn listen(&self, backlog: libc::c_int) -> io::Result<()> {
         syscall!(listen(self.as_raw_fd(), backlog))?;
         Ok(())
     }
+
+    /// Returns a reference for querying and setting socket options.
+    ///
+    /// Fails for sockets in the fixed-file table, which have no descriptor.
+    pub(crate) fn sock_ref(&self) -> io::Result<socket2::SockRef<'_>> {
+        if self.fd.fixed().is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "not supported on fixed-file sockets",
+            ));
+        }
+
+        Ok(socket2::SockRef::from(self))
+    }
+
+    pub(crate) fn local_addr(&self) -> io::Result<SocketAddr> {
+        inet_addr(self.sock_ref()?.local_addr()?)
+    }
+
+    pub(crate) fn peer_addr(&self) -> io::Result<SocketAddr> {
+        inet_addr(self.sock_ref()?.peer_addr()?)
+    }
+
+    pub(crate) fn local_addr_unix(&self) -> io::Result<socket2::SockAddr> {
+        self.sock_ref()?.local_addr()
+    }
+
+    pub(crate) fn peer_addr_unix(&self) -> io::Result<socket2::SockAddr> {
+        self.sock_ref()?.peer_addr()
+    }
+
+    pub(crate) fn setsockopt<T>(
+        &self,
+        level: libc::c_int,
+        name: libc::c_int,
+        value: T,
+    ) -> io::Result<()> {
+        syscall!(setsockopt(
+            self.as_raw_fd(),
+            level,
+            name,
+            &value as *const T as *const libc::c_void,
+            std::mem::size_of::<T>() as libc::socklen_t,
+        ))?;
+        Ok(())
+    }
+
+    pub(crate) fn getsockopt<T>(&self, level: libc::c_int, name: libc::c_int) -> io::Result<T> {
+        let mut value = std::mem::MaybeUninit::<T>::zeroed();
+        let mut len = std::mem::size_of::<T>() as libc::socklen_t;
+        syscall!(getsockopt(
+            self.as_raw_fd(),
+            level,
+            name,
+            value.as_mut_ptr().cast(),
+            &mut len,
+        ))?;
+
+        // Safety: the value was zeroed, and then written by the kernel.
+        Ok(unsafe { value.assume_init() })
+    }
+}
+
+fn inet_addr(addr: socket2::SockAddr) -> io::Result<SocketAddr> {
+    addr.as_socket()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not an IP socket address"))
 }
 
 impl AsRawFd for Socket {
@@ -0,0 +1,35 @@
+use crate::driver::{self, Op};
+
+use io_uring::types::Timespec;
+use std::{io, time::Duration};
+
+/// Fires after a relative duration.
+pub(crate) struct Timeout {
+    /// The kernel reads the timespec when preparing the request, which may
+    /// happen after the submission returned.
+    #[allow(dead_code)]
+    timespec: Box<Timespec>,
+}
+
+impl Op<Timeout> {
+    /// Submit a timeout firing after `duration`.
+    pub(crate) fn timeout(duration: Duration) -> io::Result<Op<Timeout>> {
+        use io_uring::opcode;
+
+        Op::submit_with(
+            Timeout {
+                timespec: Box::new(driver::util::timespec(duration)),
+            },
+            |timeout| opcode::Timeout::new(&*timeout.timespec).build(),
+        )
+    }
+
+    /// Ask the kernel to remove the timeout if it has not fired yet.
+    ///
+    /// The operation then completes with `ECANCELED`.
+    pub(crate) fn remove(&mut self) {
+        use io_uring::opcode;
+
+        self.cancel_with(|index| opcode::TimeoutRemove::new(index as _).build());
+    }
+}
@@ -1,6 +1,13 @@
-use std::{ffi::CString, io, path::Path};
+use io_uring::types::Timespec;
+use std::{ffi::CString, io, path::Path, time::Duration};
 
 pub(super) fn cstr(p: &Path) -> io::Result<CString> {
     use std::os::unix::ffi::OsStrExt;
     Ok(CString::new(p.as_os_str().as_bytes())?)
 }
+
+pub(super) fn timespec(duration: Duration) -> Timespec {
+    Timespec::new()
+        .sec(duration.as_secs())
+        .nsec(duration.subsec_nanos())
+}
@@ -6,6 +6,7 @@ use crate::{
 use std::{
     io,
     task::{Context, Poll},
+    time::Duration,
 };
 
 pub(crate) struct Write<T> {
@@ -19,21 +20,58 @@ pub(crate) struct Write<T> {
 
 impl<T: IoBuf> Op<Write<T>> {
     pub(crate) fn write_at(fd: &SharedFd, buf: T, offset: u64) -> io::Result<Op<Write<T>>> {
-        use io_uring::{opcode, types};
+        Op::write_at_with_deadline(fd, buf, offset, None)
+    }
 
-        Op::submit_with(
+    /// Submit a write which is cancelled by the kernel if it does not
+    /// complete within `deadline`.
+    pub(crate) fn write_at_with_deadline(
+        fd: &SharedFd,
+        buf: T,
+        offset: u64,
+        deadline: Option<Duration>,
+    ) -> io::Result<Op<Write<T>>> {
+        use io_uring::opcode;
+
+        Op::submit_with_deadline(
             Write {
                 fd: fd.clone(),
                 buf,
             },
+            deadline,
             |write| {
                 // Get raw buffer info
                 let ptr = write.buf.stable_ptr();
                 let len = write.buf.bytes_init();
 
-                opcode::Write::new(types::Fd(fd.raw_fd()), ptr, len as _)
+                with_target!(fd, |fd| opcode::Write::new(fd, ptr, len as _)
                     .offset(offset as _)
-                    .build()
+                    .build())
+            },
+        )
+    }
+
+    /// Submit a `send` with `flags` (`MSG_*`).
+    pub(crate) fn send_with_flags(
+        fd: &SharedFd,
+        buf: T,
+        flags: libc::c_int,
+    ) -> io::Result<Op<Write<T>>> {
+        use io_uring::opcode;
+
+        Op::submit_with(
+            Write {
+                fd: fd.clone(),
+                buf,
+            },
+            |write| {
+                // Get raw buffer info
+                let ptr = write.buf.stable_ptr();
+                let len = write.buf.bytes_init();
+
+                with_target!(fd, |fd| opcode::Send::new(fd, ptr, len as _)
+                    .flags(flags)
+                    .build())
             },
         )
     }
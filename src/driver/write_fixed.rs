@@ -0,0 +1,48 @@
+use crate::{
+    buf::{fixed::FixedBuf, IoBuf},
+    driver::{Op, SharedFd},
+    BufResult,
+};
+use std::io;
+
+pub(crate) struct WriteFixed {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    pub(crate) buf: FixedBuf,
+}
+
+impl Op<WriteFixed> {
+    pub(crate) fn write_fixed_at(
+        fd: &SharedFd,
+        buf: FixedBuf,
+        offset: u64,
+    ) -> io::Result<Op<WriteFixed>> {
+        use io_uring::opcode;
+
+        Op::submit_with(
+            WriteFixed {
+                fd: fd.clone(),
+                buf,
+            },
+            |write| {
+                // Get raw buffer info
+                let ptr = write.buf.stable_ptr();
+                let len = write.buf.bytes_init();
+                let buf_index = write.buf.buf_index();
+                with_target!(fd, |fd| opcode::WriteFixed::new(
+                    fd, ptr, len as _, buf_index
+                )
+                .offset(offset as _)
+                .build())
+            },
+        )
+    }
+
+    pub(crate) async fn write(self) -> BufResult<usize, FixedBuf> {
+        let complete = self.await;
+        (complete.result.map(|v| v as _), complete.data.buf)
+    }
+}
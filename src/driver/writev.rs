@@ -0,0 +1,59 @@
+use crate::{
+    buf::IoBuf,
+    driver::{Op, SharedFd},
+    BufResult,
+};
+use std::io;
+
+pub(crate) struct Writev<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(dead_code)]
+    fd: SharedFd,
+
+    pub(crate) bufs: Vec<T>,
+
+    /// One entry per buffer, read by the kernel when the operation is
+    /// prepared.
+    iovecs: Vec<libc::iovec>,
+}
+
+impl<T: IoBuf> Op<Writev<T>> {
+    pub(crate) fn writev_at(fd: &SharedFd, bufs: Vec<T>, offset: u64) -> io::Result<Op<Writev<T>>> {
+        use io_uring::opcode;
+
+        let iovecs = iovecs(&bufs);
+
+        Op::submit_with(
+            Writev {
+                fd: fd.clone(),
+                bufs,
+                iovecs,
+            },
+            |write| {
+                with_target!(fd, |fd| opcode::Writev::new(
+                    fd,
+                    write.iovecs.as_ptr(),
+                    write.iovecs.len() as _
+                )
+                .offset(offset as _)
+                .build())
+            },
+        )
+    }
+
+    pub(crate) async fn write(self) -> BufResult<usize, Vec<T>> {
+        let complete = self.await;
+        (complete.result.map(|v| v as _), complete.data.bufs)
+    }
+}
+
+/// Returns an entry for the initialized bytes of each buffer.
+pub(crate) fn iovecs<T: IoBuf>(bufs: &[T]) -> Vec<libc::iovec> {
+    bufs.iter()
+        .map(|buf| libc::iovec {
+            iov_base: buf.stable_ptr() as *mut _,
+            iov_len: buf.bytes_init(),
+        })
+        .collect()
+}
@@ -1,5 +1,6 @@
 use crate::{
-    buf::{IoBuf, IoBufMut},
+    buf::{fixed::FixedBuf, IoBuf, IoBufMut},
+    compat::Compat,
     driver::{Op, SharedFd},
     fs::OpenOptions,
 };
@@ -173,6 +174,87 @@ impl File {
         op.read().await
     }
 
+    /// Like [`read_at`], but using a buffer registered with the kernel.
+    ///
+    /// The buffer must belong to a [`FixedBufRegistry`] registered with the
+    /// current runtime, otherwise the read fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_uring::{buf::fixed::FixedBufRegistry, fs::File};
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     async_uring::start(async {
+    ///         let registry = FixedBufRegistry::new([Vec::with_capacity(10)]);
+    ///         registry.register()?;
+    ///
+    ///         let f = File::open("foo.txt").await?;
+    ///         let buffer = registry.check_out(0).unwrap();
+    ///
+    ///         // Read up to 10 bytes
+    ///         let (res, buffer) = f.read_fixed_at(buffer, 0).await;
+    ///         let n = res?;
+    ///
+    ///         println!("The bytes: {:?}", &buffer[..n]);
+    ///
+    ///         // Close the file
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// [`read_at`]: File::read_at
+    /// [`FixedBufRegistry`]: crate::buf::fixed::FixedBufRegistry
+    pub async fn read_fixed_at(
+        &self,
+        buf: FixedBuf,
+        pos: u64,
+    ) -> crate::BufResult<usize, FixedBuf> {
+        let op = Op::read_fixed_at(&self.fd, buf, pos).unwrap();
+        op.read().await
+    }
+
+    /// Read some bytes at the specified offset from the file into `bufs`,
+    /// filling them in order.
+    ///
+    /// Like [`read_at`], but scattering the data over several buffers with a
+    /// single `readv`, e.g. to read a header and a body into separate
+    /// buffers. The initialized length of each buffer is updated to the bytes
+    /// read into it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_uring::fs::File;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     async_uring::start(async {
+    ///         let f = File::open("foo.txt").await?;
+    ///
+    ///         let bufs = vec![Vec::with_capacity(4), Vec::with_capacity(1024)];
+    ///         let (res, bufs) = f.readv_at(bufs, 0).await;
+    ///         res?;
+    ///
+    ///         println!("header: {:?}, body: {:?}", &bufs[0][..], &bufs[1][..]);
+    ///
+    ///         f.close().await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// [`read_at`]: File::read_at
+    pub async fn readv_at<T: IoBufMut>(
+        &self,
+        bufs: Vec<T>,
+        pos: u64,
+    ) -> crate::BufResult<usize, Vec<T>> {
+        let op = Op::readv_at(&self.fd, bufs, pos).unwrap();
+        op.read().await
+    }
+
     /// Read the exact number of bytes required to fill `buf` at the specified
     /// offset from the file.
     ///
@@ -307,6 +389,39 @@ impl File {
         op.write().await
     }
 
+    /// Like [`write_at`], but using a buffer registered with the kernel.
+    ///
+    /// The buffer must belong to a [`FixedBufRegistry`] registered with the
+    /// current runtime, otherwise the write fails.
+    ///
+    /// [`write_at`]: File::write_at
+    /// [`FixedBufRegistry`]: crate::buf::fixed::FixedBufRegistry
+    pub async fn write_fixed_at(
+        &self,
+        buf: FixedBuf,
+        pos: u64,
+    ) -> crate::BufResult<usize, FixedBuf> {
+        let op = Op::write_fixed_at(&self.fd, buf, pos).unwrap();
+        op.write().await
+    }
+
+    /// Write the initialized bytes of `bufs`, in order, into the file at the
+    /// specified offset.
+    ///
+    /// Like [`write_at`], but gathering the data from several buffers with a
+    /// single `writev`, so separately built parts need not be concatenated.
+    /// As with `write_at`, only a prefix of the data may be written.
+    ///
+    /// [`write_at`]: File::write_at
+    pub async fn writev_at<T: IoBuf>(
+        &self,
+        bufs: Vec<T>,
+        pos: u64,
+    ) -> crate::BufResult<usize, Vec<T>> {
+        let op = Op::writev_at(&self.fd, bufs, pos).unwrap();
+        op.write().await
+    }
+
     /// Attempts to write an entire buffer into this file at the specified offset.
     ///
     /// This method will continuously call [`write_at`] until there is no more data
@@ -456,11 +571,44 @@ impl File {
         Ok(())
     }
 
+    /// Moves the file into the fixed-file table of the runtime.
+    ///
+    /// The file is installed into a free slot and its regular descriptor is
+    /// closed. Operations on the returned file skip the descriptor lookup in
+    /// the kernel. Fails if the runtime was not built with
+    /// [`Builder::fixed_files`] or the table is full, in which case the file
+    /// is closed.
+    ///
+    /// The returned file has no regular descriptor, so it cannot be passed to
+    /// system calls or other processes: its [`AsRawFd`] implementation returns
+    /// `-1`, on which system calls fail with `EBADF`.
+    ///
+    /// [`Builder::fixed_files`]: crate::Builder::fixed_files
+    pub async fn into_fixed(self) -> io::Result<File> {
+        if self.fd.fixed().is_some() {
+            return Ok(self);
+        }
+
+        let op = Op::install_fixed(&self.fd)?;
+        let completion = op.await;
+        completion.result?;
+
+        let fd = SharedFd::new_fixed(completion.data.fds[0] as u32);
+        drop(completion.data);
+
+        // The table holds its own reference to the open file.
+        self.close().await?;
+        Ok(File::from_shared_fd(fd))
+    }
+
     /// Closes the file.
     ///
     /// The method completes once the close operation has completed,
     /// guaranteeing that resources associated with the file have been released.
     ///
+    /// The close is submitted once no operation still uses the descriptor, e.g.
+    /// one whose future was dropped before it completed.
+    ///
     /// If `close` is not called before dropping the file, the file is closed in
     /// the background, but there is no guarantee as to **when** the close
     /// operation will complete.
@@ -482,11 +630,20 @@ impl File {
     /// }
     /// ```
     pub async fn close(self) -> io::Result<()> {
-        self.fd.close().await;
-        Ok(())
+        self.fd.close().await
+    }
+
+    /// Wraps the file to implement the `futures-io` traits, see [`Compat`].
+    ///
+    /// The file is read and written from a cursor starting at offset 0.
+    ///
+    /// [`Compat`]: crate::compat::Compat
+    pub fn compat(self) -> Compat<File> {
+        Compat::new(self, |file| &file.fd, true)
     }
 }
 
+/// Returns `-1` for a file in the fixed-file table, see [`File::into_fixed`].
 impl AsRawFd for File {
     fn as_raw_fd(&self) -> RawFd {
         self.fd.raw_fd()
@@ -495,9 +652,12 @@ impl AsRawFd for File {
 
 impl fmt::Debug for File {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("File")
-            .field("fd", &self.fd.raw_fd())
-            .finish()
+        let mut f = f.debug_struct("File");
+        match self.fd.fixed() {
+            Some(index) => f.field("fixed", &index),
+            None => f.field("fd", &self.fd.raw_fd()),
+        };
+        f.finish()
     }
 }
 
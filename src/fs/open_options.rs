@@ -62,6 +62,7 @@ pub struct OpenOptions {
     truncate: bool,
     create: bool,
     create_new: bool,
+    pub(crate) fixed: bool,
     pub(crate) mode: libc::mode_t,
 }
 
@@ -85,6 +86,7 @@ impl OpenOptions {
     ///     })
     /// }
     /// ```
+    #[allow(clippy::new_without_default)]
     pub fn new() -> OpenOptions {
         OpenOptions {
             // generic
@@ -94,6 +96,7 @@ impl OpenOptions {
             truncate: false,
             create: false,
             create_new: false,
+            fixed: false,
             mode: 0o666,
         }
     }
@@ -280,6 +283,40 @@ impl OpenOptions {
         self
     }
 
+    /// Sets the option to open the file directly into the fixed-file table of
+    /// the runtime.
+    ///
+    /// Operations on a fixed file skip the descriptor lookup in the kernel.
+    /// The file has no regular descriptor, so it cannot be passed to system
+    /// calls or other processes: its `AsRawFd` implementation returns `-1`,
+    /// on which system calls fail with `EBADF`. The runtime must be built with
+    /// [`Builder::fixed_files`], otherwise opening fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_uring::{fs::OpenOptions, Builder};
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rt = Builder::new().fixed_files(1024).build()?;
+    ///
+    ///     rt.block_on(async {
+    ///         let file = OpenOptions::new()
+    ///             .read(true)
+    ///             .fixed_file(true)
+    ///             .open("foo.txt")
+    ///             .await?;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// [`Builder::fixed_files`]: crate::Builder::fixed_files
+    pub fn fixed_file(&mut self, fixed: bool) -> &mut OpenOptions {
+        self.fixed = fixed;
+        self
+    }
+
     /// Opens a file at `path` with the options specified by `self`.
     ///
     /// # Errors
@@ -336,8 +373,14 @@ impl OpenOptions {
         // Await the completion of the event
         let completion = op.await;
 
-        // The file is open
-        Ok(File::from_shared_fd(SharedFd::new(completion.result? as _)))
+        // The file is open, the result is the descriptor or the fixed slot
+        let fd = completion.result?;
+        let fd = if self.fixed {
+            SharedFd::new_fixed(fd)
+        } else {
+            SharedFd::new(fd as _)
+        };
+        Ok(File::from_shared_fd(fd))
     }
 
     pub(crate) fn access_mode(&self) -> io::Result<libc::c_int> {
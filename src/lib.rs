@@ -17,13 +17,15 @@ mod runtime;
 mod utils;
 
 pub mod buf;
+pub mod compat;
 pub mod fs;
 pub mod net;
+pub mod time;
 
-pub use runtime::spawn;
+pub use runtime::{spawn, Builder, Handle, Runtime, ShardedRuntime};
 
 pub fn start<F: std::future::Future>(future: F) -> F::Output {
-    let mut rt = runtime::Runtime::new().unwrap();
+    let mut rt = Runtime::new().unwrap();
     rt.block_on(future)
 }
 
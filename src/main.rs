@@ -1,10 +1,9 @@
-use async_io::Timer;
 use async_uring::*;
 use std::time::Duration;
 
 fn main() {
     start(async {
-        Timer::after(Duration::from_secs(1)).await;
+        time::sleep(Duration::from_secs(1)).await;
         println!("Hello");
     });
 }
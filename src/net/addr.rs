@@ -0,0 +1,159 @@
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+};
+
+/// Values which can be converted or resolved to one or more socket addresses.
+///
+/// This is the counterpart of [`std::net::ToSocketAddrs`], implemented for the
+/// same types. Addresses and IP literals are converted without a lookup, while
+/// host names are resolved on a blocking thread pool, so a lookup does not
+/// stall the runtime. Synchronous constructors, such as [`TcpListener::bind`],
+/// only accept IP literals.
+///
+/// [`TcpListener::bind`]: crate::net::TcpListener::bind
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_uring::net::TcpStream;
+///
+/// fn main() -> std::io::Result<()> {
+///     async_uring::start(async {
+///         let stream = TcpStream::connect("db.internal:5432").await?;
+///         let stream = TcpStream::connect(("127.0.0.1", 5432)).await?;
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+pub trait ToSocketAddrs: sealed::ToSocketAddrsPriv {}
+
+/// Resolves `addr`, looking host names up on a blocking thread pool.
+pub(crate) async fn resolve<A: ToSocketAddrs>(addr: A) -> io::Result<Vec<SocketAddr>> {
+    match addr.to_addrs() {
+        sealed::Addrs::Ready(addrs) => Ok(addrs),
+        sealed::Addrs::Lookup(host) => blocking::unblock(move || lookup(&host)).await,
+    }
+}
+
+/// Converts `addr` without a lookup, for synchronous constructors, which
+/// cannot wait for the blocking thread pool. Host names are rejected.
+pub(crate) fn resolve_literal<A: ToSocketAddrs>(addr: A) -> io::Result<Vec<SocketAddr>> {
+    match addr.to_addrs() {
+        sealed::Addrs::Ready(addrs) => Ok(addrs),
+        sealed::Addrs::Lookup(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "host names cannot be resolved synchronously, pass an IP address",
+        )),
+    }
+}
+
+fn lookup(host: &str) -> io::Result<Vec<SocketAddr>> {
+    Ok(std::net::ToSocketAddrs::to_socket_addrs(host)?.collect())
+}
+
+/// Calls `f` with each address in turn, returning the first success or the
+/// last error.
+pub(crate) async fn each_addr<T, F, Fut>(addrs: Vec<SocketAddr>, mut f: F) -> io::Result<T>
+where
+    F: FnMut(SocketAddr) -> Fut,
+    Fut: std::future::Future<Output = io::Result<T>>,
+{
+    let mut last_err = None;
+
+    for addr in addrs {
+        match f(addr).await {
+            Ok(value) => return Ok(value),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(no_addresses))
+}
+
+/// The synchronous counterpart of [`each_addr`].
+pub(crate) fn each_addr_blocking<T, F>(addrs: Vec<SocketAddr>, mut f: F) -> io::Result<T>
+where
+    F: FnMut(SocketAddr) -> io::Result<T>,
+{
+    let mut last_err = None;
+
+    for addr in addrs {
+        match f(addr) {
+            Ok(value) => return Ok(value),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(no_addresses))
+}
+
+fn no_addresses() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "could not resolve to any addresses",
+    )
+}
+
+mod sealed {
+    use std::net::SocketAddr;
+
+    /// Addresses, or a host name still to be looked up.
+    pub enum Addrs {
+        Ready(Vec<SocketAddr>),
+        Lookup(String),
+    }
+
+    pub trait ToSocketAddrsPriv {
+        fn to_addrs(&self) -> Addrs;
+    }
+}
+
+use sealed::{Addrs, ToSocketAddrsPriv};
+
+macro_rules! to_socket_addrs {
+    ($($ty:ty => |$addr:pat_param| $body:expr;)*) => {
+        $(
+            impl ToSocketAddrs for $ty {}
+
+            impl ToSocketAddrsPriv for $ty {
+                fn to_addrs(&self) -> Addrs {
+                    let $addr = self;
+                    $body
+                }
+            }
+        )*
+    };
+}
+
+to_socket_addrs! {
+    SocketAddr => |addr| Addrs::Ready(vec![*addr]);
+    SocketAddrV4 => |addr| Addrs::Ready(vec![SocketAddr::V4(*addr)]);
+    SocketAddrV6 => |addr| Addrs::Ready(vec![SocketAddr::V6(*addr)]);
+    (IpAddr, u16) => |addr| Addrs::Ready(vec![SocketAddr::from(*addr)]);
+    (Ipv4Addr, u16) => |addr| Addrs::Ready(vec![SocketAddr::from(*addr)]);
+    (Ipv6Addr, u16) => |addr| Addrs::Ready(vec![SocketAddr::from(*addr)]);
+    [SocketAddr] => |addrs| Addrs::Ready(addrs.to_vec());
+    Vec<SocketAddr> => |addrs| Addrs::Ready(addrs.clone());
+    str => |host| match host.parse() {
+        Ok(addr) => Addrs::Ready(vec![addr]),
+        Err(_) => Addrs::Lookup(host.to_owned()),
+    };
+    String => |host| host.as_str().to_addrs();
+    (&str, u16) => |(host, port)| match host.parse::<IpAddr>() {
+        Ok(ip) => Addrs::Ready(vec![SocketAddr::new(ip, *port)]),
+        Err(_) => Addrs::Lookup(format!("{}:{}", host, port)),
+    };
+    (String, u16) => |(host, port)| (host.as_str(), *port).to_addrs();
+}
+
+impl<T: ToSocketAddrs + ?Sized> ToSocketAddrs for &T {}
+
+impl<T: ToSocketAddrs + ?Sized> ToSocketAddrsPriv for &T {
+    fn to_addrs(&self) -> Addrs {
+        (**self).to_addrs()
+    }
+}
@@ -0,0 +1,215 @@
+use std::{fmt, mem, os::unix::io::RawFd, ptr};
+
+/// A buffer of control messages (ancillary data), see `cmsg(3)`.
+///
+/// To send control messages, push them to an empty buffer and pass it to a
+/// `send_msg_with_control` method. To receive them, pass a buffer with enough
+/// capacity, see [`space`], to a `recv_msg` method and iterate over the
+/// returned buffer.
+///
+/// [`space`]: ControlMessages::space
+///
+/// # Examples
+///
+/// Passing a file descriptor over a Unix socket:
+///
+/// ```no_run
+/// use async_uring::net::{ControlMessages, UnixStream};
+/// use std::os::unix::io::AsRawFd;
+///
+/// fn main() -> std::io::Result<()> {
+///     async_uring::start(async {
+///         let stream = UnixStream::connect("/tmp/restart.sock").await?;
+///         let file = std::fs::File::open("/etc/hosts")?;
+///
+///         let mut control = ControlMessages::new();
+///         control.push_scm_rights(&[file.as_raw_fd()]);
+///
+///         let (res, _) = stream
+///             .send_msg_with_control(vec![&b"fd"[..]], control)
+///             .await;
+///         res?;
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct ControlMessages {
+    buf: Vec<u8>,
+    truncated: bool,
+}
+
+/// A control message, yielded by [`ControlMessages::iter`].
+#[derive(Clone, Copy)]
+pub struct ControlMessage<'a> {
+    level: libc::c_int,
+    ty: libc::c_int,
+    data: &'a [u8],
+}
+
+impl ControlMessages {
+    /// Creates an empty buffer.
+    pub fn new() -> ControlMessages {
+        ControlMessages::default()
+    }
+
+    /// Creates an empty buffer able to receive `capacity` bytes of control
+    /// messages.
+    pub fn with_capacity(capacity: usize) -> ControlMessages {
+        ControlMessages {
+            buf: Vec::with_capacity(capacity),
+            truncated: false,
+        }
+    }
+
+    /// Returns the space taken by a control message carrying `len` bytes of
+    /// data (`CMSG_SPACE`).
+    pub fn space(len: usize) -> usize {
+        unsafe { libc::CMSG_SPACE(len as _) as usize }
+    }
+
+    /// Appends a control message.
+    pub fn push(&mut self, level: libc::c_int, ty: libc::c_int, data: &[u8]) {
+        let start = self.buf.len();
+        self.buf
+            .resize(start + ControlMessages::space(data.len()), 0);
+
+        let header = libc::cmsghdr {
+            cmsg_len: unsafe { libc::CMSG_LEN(data.len() as _) } as _,
+            cmsg_level: level,
+            cmsg_type: ty,
+        };
+        let data_start = start + header_len();
+
+        // Safety: the buffer was resized to hold the header and the data. It
+        // is not aligned, hence the unaligned write.
+        unsafe {
+            ptr::write_unaligned(self.buf[start..].as_mut_ptr().cast(), header);
+        }
+        self.buf[data_start..data_start + data.len()].copy_from_slice(data);
+    }
+
+    /// Appends an `SCM_RIGHTS` message passing `fds` to the peer of a Unix
+    /// socket.
+    ///
+    /// The descriptors must stay open until the message was sent.
+    pub fn push_scm_rights(&mut self, fds: &[RawFd]) {
+        let data: Vec<u8> = fds.iter().flat_map(|fd| fd.to_ne_bytes()).collect();
+        self.push(libc::SOL_SOCKET, libc::SCM_RIGHTS, &data);
+    }
+
+    /// Returns an iterator over the control messages.
+    pub fn iter(&self) -> impl Iterator<Item = ControlMessage<'_>> {
+        let mut buf = &self.buf[..];
+
+        std::iter::from_fn(move || {
+            if buf.len() < mem::size_of::<libc::cmsghdr>() {
+                return None;
+            }
+
+            // Safety: the length was checked above.
+            let header: libc::cmsghdr = unsafe { ptr::read_unaligned(buf.as_ptr().cast()) };
+            let len = (header.cmsg_len as usize).checked_sub(header_len())?;
+            let data = buf.get(header_len()..header_len() + len)?;
+
+            buf = buf.get(ControlMessages::space(len)..).unwrap_or(&[]);
+
+            Some(ControlMessage {
+                level: header.cmsg_level,
+                ty: header.cmsg_type,
+                data,
+            })
+        })
+    }
+
+    /// Returns the number of bytes of control messages.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns `true` if the buffer holds no control message.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns `true` if received control messages did not fit in the buffer
+    /// (`MSG_CTRUNC`). Truncated `SCM_RIGHTS` descriptors are closed by the
+    /// kernel.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Removes all control messages, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.truncated = false;
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<u8> {
+        self.buf
+    }
+
+    pub(crate) fn from_raw(buf: Vec<u8>, flags: libc::c_int) -> ControlMessages {
+        ControlMessages {
+            buf,
+            truncated: flags & libc::MSG_CTRUNC != 0,
+        }
+    }
+}
+
+impl<'a> ControlMessage<'a> {
+    /// Returns the originating protocol, e.g. `SOL_SOCKET`.
+    pub fn level(&self) -> libc::c_int {
+        self.level
+    }
+
+    /// Returns the protocol-specific type, e.g. `SCM_RIGHTS`.
+    pub fn ty(&self) -> libc::c_int {
+        self.ty
+    }
+
+    /// Returns the data of the message.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the descriptors passed by an `SCM_RIGHTS` message.
+    ///
+    /// The descriptors were installed in this process when the message was
+    /// received, with close-on-exec set. The caller owns them and must close
+    /// them.
+    pub fn scm_rights(&self) -> Option<Vec<RawFd>> {
+        if self.level != libc::SOL_SOCKET || self.ty != libc::SCM_RIGHTS {
+            return None;
+        }
+
+        let fds = self
+            .data
+            .chunks_exact(mem::size_of::<RawFd>())
+            .map(|fd| RawFd::from_ne_bytes(fd.try_into().unwrap()))
+            .collect();
+        Some(fds)
+    }
+}
+
+/// Length of a control message header, including padding (`CMSG_LEN(0)`).
+fn header_len() -> usize {
+    unsafe { libc::CMSG_LEN(0) as usize }
+}
+
+impl fmt::Debug for ControlMessages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl fmt::Debug for ControlMessage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ControlMessage")
+            .field("level", &self.level)
+            .field("ty", &self.ty)
+            .field("data", &self.data)
+            .finish()
+    }
+}
@@ -0,0 +1,116 @@
+use std::{fmt, ops};
+
+macro_rules! msg_flags {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$flag_meta:meta])*
+                const $flag:ident = $value:expr;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name(libc::c_int);
+
+        impl $name {
+            $(
+                $(#[$flag_meta])*
+                pub const $flag: $name = $name($value);
+            )*
+
+            /// Returns a set without any flag.
+            pub const fn empty() -> $name {
+                $name(0)
+            }
+
+            /// Returns the raw `MSG_*` bits.
+            pub const fn bits(self) -> libc::c_int {
+                self.0
+            }
+
+            /// Returns `true` if all flags of `other` are set.
+            pub const fn contains(self, other: $name) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl ops::BitOr for $name {
+            type Output = $name;
+
+            fn bitor(self, rhs: $name) -> $name {
+                $name(self.0 | rhs.0)
+            }
+        }
+
+        impl ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: $name) {
+                self.0 |= rhs.0;
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut set = f.debug_set();
+                $(
+                    if self.0 & $value != 0 {
+                        set.entry(&format_args!(stringify!($flag)));
+                    }
+                )*
+                set.finish()
+            }
+        }
+    };
+}
+
+msg_flags! {
+    /// Flags of a send, see `send(2)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_uring::net::SendFlags;
+    ///
+    /// let flags = SendFlags::NOSIGNAL | SendFlags::MORE;
+    /// assert!(flags.contains(SendFlags::MORE));
+    /// ```
+    pub struct SendFlags {
+        /// Do not raise `SIGPIPE` when the peer closed the connection, the
+        /// send fails with `EPIPE` instead (`MSG_NOSIGNAL`).
+        const NOSIGNAL = libc::MSG_NOSIGNAL;
+
+        /// More data follows, the kernel may hold back a partial segment
+        /// (`MSG_MORE`).
+        const MORE = libc::MSG_MORE;
+
+        /// Fail with `EAGAIN` instead of waiting for room in the socket
+        /// buffer (`MSG_DONTWAIT`).
+        const DONTWAIT = libc::MSG_DONTWAIT;
+    }
+}
+
+msg_flags! {
+    /// Flags of a receive, see `recv(2)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_uring::net::RecvFlags;
+    ///
+    /// let flags = RecvFlags::PEEK;
+    /// assert!(!flags.contains(RecvFlags::WAITALL));
+    /// ```
+    pub struct RecvFlags {
+        /// Return the data without removing it from the receive queue
+        /// (`MSG_PEEK`).
+        const PEEK = libc::MSG_PEEK;
+
+        /// Wait until the whole buffer is filled, unless the stream ends or
+        /// an error occurs (`MSG_WAITALL`).
+        const WAITALL = libc::MSG_WAITALL;
+
+        /// Fail with `EAGAIN` instead of waiting for data (`MSG_DONTWAIT`).
+        const DONTWAIT = libc::MSG_DONTWAIT;
+    }
+}
@@ -6,17 +6,30 @@
 //! # Organization
 //!
 //! * [`TcpListener`] and [`TcpStream`] provide functionality for communication over TCP
+//! * [`TcpSocket`] configures a TCP socket before it listens or connects
 //! * [`UdpSocket`] provides functionality for communication over UDP
+//! * [`UnixListener`], [`UnixStream`] and [`UnixDatagram`] provide functionality for
+//!   communication over Unix domain sockets
 
 //!
 //! [`TcpListener`]: TcpListener
 //! [`TcpStream`]: TcpStream
+//! [`TcpSocket`]: TcpSocket
 //! [`UdpSocket`]: UdpSocket
+//! [`UnixListener`]: UnixListener
+//! [`UnixStream`]: UnixStream
+//! [`UnixDatagram`]: UnixDatagram
 
+mod addr;
+mod cmsg;
+mod flags;
 mod tcp;
 mod udp;
 mod unix;
 
-pub use tcp::{TcpListener, TcpStream};
-pub use udp::UdpSocket;
-pub use unix::{UnixListener, UnixStream};
+pub use addr::ToSocketAddrs;
+pub use cmsg::{ControlMessage, ControlMessages};
+pub use flags::{RecvFlags, SendFlags};
+pub use tcp::{RecvStream, TcpIncoming, TcpListener, TcpSocket, TcpStream};
+pub use udp::{RecvMsgStream, UdpSocket};
+pub use unix::{UCred, UnixDatagram, UnixIncoming, UnixListener, UnixSocketAddr, UnixStream};
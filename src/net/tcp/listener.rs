@@ -1,6 +1,15 @@
 use super::TcpStream;
-use crate::driver::Socket;
-use std::{io, net::SocketAddr};
+use crate::{
+    driver::{self, Socket},
+    net::{addr, ToSocketAddrs},
+};
+use futures_lite::Stream;
+use std::{
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 /// A TCP socket server, listening for connections.
 ///
@@ -14,10 +23,10 @@ use std::{io, net::SocketAddr};
 /// use async_uring::net::TcpStream;
 ///
 ///
-/// let listener = TcpListener::bind("127.0.0.1:2345".parse().unwrap()).unwrap();
+/// let listener = TcpListener::bind("127.0.0.1:2345").unwrap();
 ///
 /// async_uring::start(async move {
-///     let tx_fut = TcpStream::connect("127.0.0.1:2345".parse().unwrap());
+///     let tx_fut = TcpStream::connect("127.0.0.1:2345");
 ///
 ///     let rx_fut = listener.accept();
 ///
@@ -33,7 +42,7 @@ use std::{io, net::SocketAddr};
 ///
 /// ```
 pub struct TcpListener {
-    inner: Socket,
+    pub(super) inner: Socket,
 }
 
 impl TcpListener {
@@ -44,12 +53,45 @@ impl TcpListener {
     /// Binding with a port number of 0 will request that the OS assigns a port
     /// to this listener.
     ///
-    /// In the future, the port allocated can be queried via a (blocking) `local_addr`
-    /// method.
-    pub fn bind(addr: SocketAddr) -> io::Result<Self> {
-        let socket = Socket::bind(addr, libc::SOCK_STREAM)?;
-        socket.listen(1024)?;
-        Ok(TcpListener { inner: socket })
+    /// The port allocated can be queried via the [`local_addr`] method.
+    ///
+    /// The socket is created with `SO_REUSEADDR` and `SO_REUSEPORT` set, and a
+    /// backlog of 1024. Whether an IPv6 listener bound to the unspecified
+    /// address also accepts IPv4 connections follows the system default. Use
+    /// [`TcpSocket`] to choose these.
+    ///
+    /// If `addr` yields several addresses, each is tried in turn until one
+    /// binds. As `bind` is synchronous, it cannot look host names up without
+    /// blocking the runtime, so `addr` must be an IP literal: host names fail
+    /// with [`ErrorKind::InvalidInput`].
+    ///
+    /// [`ErrorKind::InvalidInput`]: std::io::ErrorKind::InvalidInput
+    ///
+    /// [`local_addr`]: TcpListener::local_addr
+    /// [`TcpSocket`]: crate::net::TcpSocket
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        addr::each_addr_blocking(addr::resolve_literal(addr)?, |addr| {
+            let socket = Socket::bind(addr, libc::SOCK_STREAM)?;
+            socket.listen(1024)?;
+            Ok(TcpListener { inner: socket })
+        })
+    }
+
+    /// Returns the local address this socket is bound to.
+    ///
+    /// This is how the port assigned when binding to port 0 is learned.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Sets the time-to-live of outgoing IP packets (`IP_TTL`).
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.inner.sock_ref()?.set_ttl(ttl)
+    }
+
+    /// Returns the time-to-live of outgoing IP packets.
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.inner.sock_ref()?.ttl()
     }
 
     /// Accepts a new incoming connection from this listener.
@@ -60,11 +102,112 @@ impl TcpListener {
     ///
     /// [`TcpStream`]: struct@crate::net::TcpStream
     pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
-        let (socket, socket_addr) = self.inner.accept().await?;
+        self.accept_inner(false).await
+    }
+
+    /// Like [`accept`], but installs the connection directly into the
+    /// fixed-file table of the runtime.
+    ///
+    /// Operations on the stream skip the descriptor lookup in the kernel. The
+    /// stream has no regular descriptor, so it cannot be used with blocking
+    /// system calls. The runtime must be built with [`Builder::fixed_files`],
+    /// otherwise accepting fails.
+    ///
+    /// [`accept`]: TcpListener::accept
+    /// [`Builder::fixed_files`]: crate::Builder::fixed_files
+    pub async fn accept_fixed(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        self.accept_inner(true).await
+    }
+
+    /// Returns a stream of incoming connections.
+    ///
+    /// A single multishot accept keeps accepting connections until the stream
+    /// is dropped, instead of submitting one operation per connection. The
+    /// peer address is not reported, use [`accept`] when it is needed.
+    /// Multishot accept requires Linux 5.19.
+    ///
+    /// Connections accepted by the kernel after the stream was dropped are
+    /// closed.
+    ///
+    /// [`accept`]: TcpListener::accept
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_uring::net::TcpListener;
+    /// use futures_lite::StreamExt;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:2345").unwrap();
+    ///
+    /// async_uring::start(async move {
+    ///     let mut incoming = listener.incoming();
+    ///
+    ///     while let Some(stream) = incoming.next().await {
+    ///         let stream = stream.unwrap();
+    ///         stream.write(b"hello" as &'static [u8]).await.0.unwrap();
+    ///     }
+    /// });
+    /// ```
+    pub fn incoming(&self) -> TcpIncoming {
+        TcpIncoming {
+            inner: self.inner.incoming(),
+        }
+    }
+
+    async fn accept_inner(&self, fixed: bool) -> io::Result<(TcpStream, SocketAddr)> {
+        let (socket, socket_addr) = self.inner.accept(fixed).await?;
         let stream = TcpStream { inner: socket };
-        let socket_addr = socket_addr.ok_or_else(|| {
-            io::Error::new(io::ErrorKind::Other, "Could not get socket IP address")
-        })?;
+        let socket_addr = socket_addr
+            .as_socket()
+            .ok_or_else(|| io::Error::other("Could not get socket IP address"))?;
         Ok((stream, socket_addr))
     }
+
+    /// Closes the listener.
+    ///
+    /// The method completes once the close operation has completed,
+    /// guaranteeing that resources associated with the listener have been
+    /// released.
+    ///
+    /// The close is submitted once no operation or stream created from the
+    /// listener still uses its descriptor.
+    ///
+    /// If `close` is not called before dropping the listener, it is closed in the
+    /// background, but there is no guarantee as to **when** the close
+    /// operation will complete.
+    pub async fn close(self) -> io::Result<()> {
+        self.inner.close().await
+    }
+}
+
+/// A stream of connections accepted by a [`TcpListener`].
+///
+/// Created by [`TcpListener::incoming`]. The stream only ends once cancelled, and
+/// may be polled again after yielding an error.
+pub struct TcpIncoming {
+    inner: driver::Incoming,
+}
+
+impl TcpIncoming {
+    /// Stops accepting connections.
+    ///
+    /// Connections the kernel already accepted are still yielded, then the
+    /// stream ends. Later connections wait in the listen queue.
+    pub fn cancel(&mut self) {
+        self.inner.cancel();
+    }
+}
+
+impl Stream for TcpIncoming {
+    type Item = io::Result<TcpStream>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().inner.poll_next(cx).map(|item| {
+            item.map(|result| {
+                result.map(|fd| TcpStream {
+                    inner: Socket::from_shared_fd(fd),
+                })
+            })
+        })
+    }
 }
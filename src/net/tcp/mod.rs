@@ -1,5 +1,8 @@
 mod listener;
-pub use listener::TcpListener;
+pub use listener::{TcpIncoming, TcpListener};
+
+mod socket;
+pub use socket::TcpSocket;
 
 mod stream;
-pub use stream::TcpStream;
+pub use stream::{RecvStream, TcpStream};
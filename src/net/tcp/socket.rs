@@ -0,0 +1,153 @@
+use super::{TcpListener, TcpStream};
+use crate::driver::Socket;
+use std::{io, net::SocketAddr, time::Duration};
+
+/// A TCP socket that has not yet been converted to a [`TcpStream`] or
+/// [`TcpListener`].
+///
+/// Unlike [`TcpListener::bind`], which always sets `SO_REUSEADDR` and
+/// `SO_REUSEPORT`, no option is set unless requested. Options are applied in
+/// order, before the socket is bound, listens or connects.
+///
+/// # Examples
+///
+/// Binding a listener that fails if the port is already taken:
+///
+/// ```no_run
+/// use async_uring::net::TcpSocket;
+///
+/// fn main() -> std::io::Result<()> {
+///     async_uring::start(async {
+///         let socket = TcpSocket::new_v4()?;
+///         socket.set_reuseaddr(true)?;
+///         socket.bind("127.0.0.1:8080".parse().unwrap())?;
+///
+///         let listener = socket.listen(128)?;
+///         let (stream, _) = listener.accept().await?;
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+///
+/// Connecting from a given source address:
+///
+/// ```no_run
+/// use async_uring::net::TcpSocket;
+///
+/// fn main() -> std::io::Result<()> {
+///     async_uring::start(async {
+///         let socket = TcpSocket::new_v4()?;
+///         socket.set_nodelay(true)?;
+///         socket.bind("10.0.0.2:0".parse().unwrap())?;
+///
+///         let stream = socket.connect("10.0.0.1:8080".parse().unwrap()).await?;
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+pub struct TcpSocket {
+    inner: Socket,
+}
+
+impl TcpSocket {
+    /// Creates a new IPv4 TCP socket.
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        TcpSocket::new(libc::AF_INET)
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        TcpSocket::new(libc::AF_INET6)
+    }
+
+    fn new(domain: libc::c_int) -> io::Result<TcpSocket> {
+        let inner = Socket::new_domain(domain, libc::SOCK_STREAM)?;
+        Ok(TcpSocket { inner })
+    }
+
+    /// Allows binding to an address still in use by connections in the
+    /// `TIME_WAIT` state (`SO_REUSEADDR`).
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.inner.sock_ref()?.set_reuse_address(reuseaddr)
+    }
+
+    /// Returns the value of `SO_REUSEADDR`.
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.reuse_address()
+    }
+
+    /// Allows several sockets to bind to the same address, the kernel
+    /// balancing connections between them (`SO_REUSEPORT`).
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.inner.sock_ref()?.set_reuse_port(reuseport)
+    }
+
+    /// Returns the value of `SO_REUSEPORT`.
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.reuse_port()
+    }
+
+    /// Restricts an IPv6 socket to IPv6 traffic (`IPV6_V6ONLY`). Otherwise, an
+    /// IPv6 socket bound to the unspecified address also handles IPv4.
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.inner.sock_ref()?.set_only_v6(only_v6)
+    }
+
+    /// Returns the value of `IPV6_V6ONLY`.
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.only_v6()
+    }
+
+    /// Sets the value of `TCP_NODELAY`, see [`TcpStream::set_nodelay`].
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.sock_ref()?.set_nodelay(nodelay)
+    }
+
+    /// Sets the value of `SO_KEEPALIVE`, see [`TcpStream::set_keepalive`].
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.inner.sock_ref()?.set_keepalive(keepalive)
+    }
+
+    /// Sets the value of `SO_LINGER`, see [`TcpStream::set_linger`].
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.inner.sock_ref()?.set_linger(linger)
+    }
+
+    /// Sets the size of the send buffer (`SO_SNDBUF`).
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.sock_ref()?.set_send_buffer_size(size)
+    }
+
+    /// Sets the size of the receive buffer (`SO_RCVBUF`). Set before
+    /// connecting or listening, it takes part in the window scale
+    /// negotiation.
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.sock_ref()?.set_recv_buffer_size(size)
+    }
+
+    /// Binds the socket to the given address.
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.inner.bind_addr(addr)
+    }
+
+    /// Returns the local address of the socket, once bound.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Converts the socket into a listener, with a queue of at most `backlog`
+    /// pending connections.
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = backlog.min(libc::c_int::MAX as u32) as libc::c_int;
+        self.inner.listen(backlog)?;
+        Ok(TcpListener { inner: self.inner })
+    }
+
+    /// Connects the socket to `addr`, converting it into a stream.
+    pub async fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        self.inner.connect(socket2::SockAddr::from(addr)).await?;
+        Ok(TcpStream { inner: self.inner })
+    }
+}
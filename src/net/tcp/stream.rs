@@ -1,9 +1,18 @@
-use std::{io, net::SocketAddr};
+use std::{
+    io,
+    net::{Shutdown, SocketAddr},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use crate::{
-    buf::{IoBuf, IoBufMut},
-    driver::Socket,
+    buf::{fixed::FixedBuf, BufPool, IoBuf, IoBufMut, ProvidedBuf},
+    compat::Compat,
+    driver::{self, Socket},
+    net::{addr, RecvFlags, SendFlags, ToSocketAddrs},
 };
+use futures_lite::Stream;
 
 /// A TCP stream between a local and a remote socket.
 ///
@@ -19,7 +28,7 @@ use crate::{
 /// fn main() -> std::io::Result<()> {
 ///     async_uring::start(async {
 ///         // Connect to a peer
-///         let mut stream = TcpStream::connect("127.0.0.1:8080".parse().unwrap()).await?;
+///         let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
 ///
 ///         // Write some data.
 ///         let (result, _) = stream.write(b"hello world!".as_slice()).await;
@@ -38,12 +47,105 @@ pub struct TcpStream {
 }
 
 impl TcpStream {
-    /// Opens a TCP connection to a remote host at the given `SocketAddr`
-    pub async fn connect(addr: SocketAddr) -> io::Result<TcpStream> {
+    /// Opens a TCP connection to a remote host.
+    ///
+    /// `addr` may be a host name, which is resolved without blocking the
+    /// runtime. If it resolves to several addresses, each is tried in turn
+    /// until a connection succeeds, and the error of the last attempt is
+    /// returned otherwise.
+    pub async fn connect(addr: impl ToSocketAddrs) -> io::Result<TcpStream> {
+        let addrs = addr::resolve(addr).await?;
+        addr::each_addr(addrs, TcpStream::connect_addr).await
+    }
+
+    async fn connect_addr(addr: SocketAddr) -> io::Result<TcpStream> {
         let socket = Socket::new(addr, libc::SOCK_STREAM)?;
         socket.connect(socket2::SockAddr::from(addr)).await?;
         let tcp_stream = TcpStream { inner: socket };
-        return Ok(tcp_stream);
+        Ok(tcp_stream)
+    }
+
+    /// Returns the local address of the stream.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Returns the address of the remote peer.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
+    /// Enables or disables Nagle's algorithm (`TCP_NODELAY`).
+    ///
+    /// With `true`, small writes are sent immediately instead of being
+    /// coalesced.
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.sock_ref()?.set_nodelay(nodelay)
+    }
+
+    /// Returns `true` if Nagle's algorithm is disabled.
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.nodelay()
+    }
+
+    /// Sets the time-to-live of outgoing IP packets (`IP_TTL`).
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.inner.sock_ref()?.set_ttl(ttl)
+    }
+
+    /// Returns the time-to-live of outgoing IP packets.
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.inner.sock_ref()?.ttl()
+    }
+
+    /// Enables or disables TCP keepalive probes (`SO_KEEPALIVE`).
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.inner.sock_ref()?.set_keepalive(keepalive)
+    }
+
+    /// Returns `true` if TCP keepalive probes are enabled.
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.keepalive()
+    }
+
+    /// Sets how long closing the stream waits for unsent data to be sent
+    /// (`SO_LINGER`). With `Some(Duration::ZERO)`, closing resets the
+    /// connection.
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.inner.sock_ref()?.set_linger(linger)
+    }
+
+    /// Returns the linger duration of the stream, see [`set_linger`].
+    ///
+    /// [`set_linger`]: Self::set_linger
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.inner.sock_ref()?.linger()
+    }
+
+    /// Sets the size of the socket's send buffer (`SO_SNDBUF`). The kernel
+    /// doubles the value, and enforces a minimum.
+    pub fn set_send_buffer_size(&self, send_buffer_size: usize) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_send_buffer_size(send_buffer_size)
+    }
+
+    /// Returns the size of the socket's send buffer.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.sock_ref()?.send_buffer_size()
+    }
+
+    /// Sets the size of the socket's receive buffer (`SO_RCVBUF`). The kernel
+    /// doubles the value, and enforces a minimum.
+    pub fn set_recv_buffer_size(&self, recv_buffer_size: usize) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_recv_buffer_size(recv_buffer_size)
+    }
+
+    /// Returns the size of the socket's receive buffer.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.sock_ref()?.recv_buffer_size()
     }
 
     /// Read some data from the stream into the buffer, returning the original buffer and
@@ -57,4 +159,229 @@ impl TcpStream {
     pub async fn write<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
         self.inner.write(buf).await
     }
+
+    /// Like [`read`], but receives with `flags`.
+    ///
+    /// With [`RecvFlags::PEEK`], the data stays queued and is returned again
+    /// by the next receive.
+    ///
+    /// [`read`]: Self::read
+    pub async fn recv_with_flags<T: IoBufMut>(
+        &self,
+        buf: T,
+        flags: RecvFlags,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.recv(buf, flags.bits()).await
+    }
+
+    /// Write the initialized bytes of `bufs`, in order, to the stream with a
+    /// single `writev`, returning the buffers and the quantity of data
+    /// written.
+    ///
+    /// As with [`write`], only a prefix of the data may be written.
+    ///
+    /// [`write`]: Self::write
+    pub async fn writev<T: IoBuf>(&self, bufs: Vec<T>) -> crate::BufResult<usize, Vec<T>> {
+        self.inner.writev(bufs).await
+    }
+
+    /// Like [`write`], but sends with `flags`.
+    ///
+    /// With [`SendFlags::NOSIGNAL`], sending on a connection closed by the peer
+    /// fails with `EPIPE` instead of raising `SIGPIPE`.
+    ///
+    /// [`write`]: Self::write
+    pub async fn send_with_flags<T: IoBuf>(
+        &self,
+        buf: T,
+        flags: SendFlags,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.send(buf, flags.bits()).await
+    }
+
+    /// Like [`read`], but using a buffer registered with the kernel.
+    ///
+    /// [`read`]: Self::read
+    pub async fn read_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        self.inner.read_fixed(buf).await
+    }
+
+    /// Read some data from the stream into a buffer selected by the kernel
+    /// from `pool`.
+    ///
+    /// No buffer is held while waiting for data, which makes this suited for
+    /// many mostly idle connections. Returns `None` at the end of the stream.
+    /// Fails with `ENOBUFS` if the pool has no free buffer when the read
+    /// completes, including at the end of the stream.
+    pub async fn read_pooled(&self, pool: &BufPool) -> io::Result<Option<ProvidedBuf>> {
+        self.inner.read_pooled(pool).await
+    }
+
+    /// Returns a stream of buffers selected by the kernel from `pool` and
+    /// filled with the data arriving on the stream.
+    ///
+    /// A single multishot `recv` stays armed while data arrives, instead of
+    /// submitting one read per buffer. The stream ends at the end of the TCP
+    /// stream or after an error. When the pool has no free buffer, it yields
+    /// `ENOBUFS` and receiving resumes on the next poll. Multishot `recv`
+    /// requires Linux 6.0.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_uring::{buf::BufPool, net::TcpStream};
+    /// use futures_lite::StreamExt;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     async_uring::start(async {
+    ///         let pool = BufPool::new(64, 4096)?;
+    ///         let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    ///
+    ///         let mut bufs = stream.recv_multishot(&pool);
+    ///         while let Some(buf) = bufs.next().await {
+    ///             println!("{:?}", &buf?[..]);
+    ///         }
+    ///
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn recv_multishot(&self, pool: &BufPool) -> RecvStream {
+        RecvStream {
+            inner: self.inner.recv_multi(pool, false),
+        }
+    }
+
+    /// Sends data on the stream without copying it into the socket buffer.
+    ///
+    /// The kernel sends straight from `buf`, and hands it back only once the
+    /// network stack released it, which may be well after the data was
+    /// queued. This pays off for large buffers, for small ones [`write`] is
+    /// cheaper. Zero-copy send requires Linux 6.0.
+    ///
+    /// [`write`]: Self::write
+    pub async fn send_zc<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
+        self.inner.send_zc(buf).await
+    }
+
+    /// Like [`send_zc`], but using a buffer registered with the kernel, which
+    /// also saves mapping the buffer's pages for every send.
+    ///
+    /// [`send_zc`]: Self::send_zc
+    pub async fn send_zc_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        self.inner.send_zc_fixed(buf).await
+    }
+
+    /// Like [`write`], but using a buffer registered with the kernel.
+    ///
+    /// [`write`]: Self::write
+    pub async fn write_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        self.inner.write_fixed(buf).await
+    }
+
+    /// Like [`read`], but fails with `ErrorKind::TimedOut` if no data arrives
+    /// within `timeout`.
+    ///
+    /// The deadline is enforced by the kernel through a linked timeout, which
+    /// cancels the read. The buffer is returned in either case.
+    ///
+    /// [`read`]: Self::read
+    pub async fn read_with_timeout<T: IoBufMut>(
+        &self,
+        buf: T,
+        timeout: Duration,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.read_with_timeout(buf, timeout).await
+    }
+
+    /// Like [`write`], but fails with `ErrorKind::TimedOut` if the data
+    /// cannot be written within `timeout`.
+    ///
+    /// [`write`]: Self::write
+    pub async fn write_with_timeout<T: IoBuf>(
+        &self,
+        buf: T,
+        timeout: Duration,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.write_with_timeout(buf, timeout).await
+    }
+
+    /// Shuts down the read half, write half, or both halves of the stream.
+    ///
+    /// Shutting down the write half sends a FIN to the peer, which reads the
+    /// end of the stream, while this side can still read the response.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_uring::net::TcpStream;
+    /// use std::net::Shutdown;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     async_uring::start(async {
+    ///         let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    ///
+    ///         let (res, _) = stream.write(&b"GET / HTTP/1.0\r\n\r\n"[..]).await;
+    ///         res?;
+    ///         stream.shutdown(Shutdown::Write).await?;
+    ///
+    ///         let (res, response) = stream.read(vec![0; 4096]).await;
+    ///         println!("{:?}", &response[..res?]);
+    ///
+    ///         stream.close().await
+    ///     })
+    /// }
+    /// ```
+    pub async fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how).await
+    }
+
+    /// Closes the stream.
+    ///
+    /// The method completes once the close operation has completed,
+    /// guaranteeing that resources associated with the stream have been
+    /// released.
+    ///
+    /// The close is submitted once no operation or stream created from the
+    /// stream still uses its descriptor.
+    ///
+    /// If `close` is not called before dropping the stream, it is closed in the
+    /// background, but there is no guarantee as to **when** the close
+    /// operation will complete.
+    pub async fn close(self) -> io::Result<()> {
+        self.inner.close().await
+    }
+
+    /// Wraps the stream to implement the `futures-io` traits, see [`Compat`].
+    ///
+    /// [`Compat`]: crate::compat::Compat
+    pub fn compat(self) -> Compat<TcpStream> {
+        Compat::new(self, |stream| stream.inner.shared_fd(), false)
+    }
+}
+
+/// A stream of buffers received by [`TcpStream::recv_multishot`].
+pub struct RecvStream {
+    inner: driver::RecvStream,
+}
+
+impl RecvStream {
+    /// Stops receiving.
+    ///
+    /// Buffers the kernel already filled are still yielded, then the stream
+    /// ends.
+    pub fn cancel(&mut self) {
+        self.inner.cancel();
+    }
+}
+
+impl Stream for RecvStream {
+    type Item = io::Result<ProvidedBuf>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut()
+            .inner
+            .poll_next(cx)
+            .map(|item| item.map(|result| result.map(|(buf, _)| buf)))
+    }
 }
@@ -1,9 +1,16 @@
 use crate::{
-    buf::{IoBuf, IoBufMut},
-    driver::Socket,
+    buf::{fixed::FixedBuf, BufPool, IoBuf, IoBufMut, ProvidedBuf},
+    driver::{self, Socket},
+    net::{addr, ControlMessages, RecvFlags, SendFlags, ToSocketAddrs},
 };
+use futures_lite::Stream;
 use socket2::SockAddr;
-use std::{io, net::SocketAddr};
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6},
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 /// A UDP socket.
 ///
@@ -87,11 +94,39 @@ pub struct UdpSocket {
 
 impl UdpSocket {
     /// Creates a new UDP socket and attempt to bind it to the addr provided.
-    pub async fn bind(socket_addr: SocketAddr) -> io::Result<UdpSocket> {
-        let socket = Socket::bind(socket_addr, libc::SOCK_DGRAM)?;
+    ///
+    /// If `addr` resolves to several addresses, each is tried in turn until
+    /// one binds. Whether an IPv6 socket bound to the unspecified address also
+    /// receives IPv4 datagrams follows the system default, use [`bind_v6`] to
+    /// choose.
+    ///
+    /// [`bind_v6`]: UdpSocket::bind_v6
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<UdpSocket> {
+        let addrs = addr::resolve(addr).await?;
+        addr::each_addr(addrs, |socket_addr| async move {
+            let socket = Socket::bind(socket_addr, libc::SOCK_DGRAM)?;
+            Ok(UdpSocket { inner: socket })
+        })
+        .await
+    }
+
+    /// Creates a new IPv6 UDP socket bound to `addr`, restricted to IPv6
+    /// traffic with `only_v6` (`IPV6_V6ONLY`).
+    ///
+    /// Without `only_v6`, a socket bound to the unspecified address also
+    /// receives IPv4 datagrams, whatever the system default.
+    pub fn bind_v6(addr: SocketAddrV6, only_v6: bool) -> io::Result<UdpSocket> {
+        let socket = Socket::bind_v6(addr, libc::SOCK_DGRAM, only_v6)?;
         Ok(UdpSocket { inner: socket })
     }
 
+    /// Returns the value of `IPV6_V6ONLY`, see [`bind_v6`].
+    ///
+    /// [`bind_v6`]: UdpSocket::bind_v6
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.only_v6()
+    }
+
     /// Connects this UDP socket to a remote address, allowing the `write` and
     /// `read` syscalls to be used to send data and also applies filters to only
     /// receive data from the specified address.
@@ -99,8 +134,159 @@ impl UdpSocket {
     /// Note that usually, a successful `connect` call does not specify
     /// that there is a remote server listening on the port, rather, such an
     /// error would only be detected after the first send.
-    pub async fn connect(&self, socket_addr: SocketAddr) -> io::Result<()> {
-        self.inner.connect(SockAddr::from(socket_addr)).await
+    ///
+    /// If `addr` resolves to several addresses, each is tried in turn.
+    pub async fn connect(&self, addr: impl ToSocketAddrs) -> io::Result<()> {
+        let addrs = addr::resolve(addr).await?;
+        addr::each_addr(addrs, |socket_addr| {
+            self.inner.connect(SockAddr::from(socket_addr))
+        })
+        .await
+    }
+
+    /// Returns the local address this socket is bound to.
+    ///
+    /// This is how the port assigned when binding to port 0 is learned.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Returns the address of the remote peer the socket is connected to.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
+    /// Sets the time-to-live of outgoing IP packets (`IP_TTL`).
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.inner.sock_ref()?.set_ttl(ttl)
+    }
+
+    /// Returns the time-to-live of outgoing IP packets.
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.inner.sock_ref()?.ttl()
+    }
+
+    /// Enables or disables sending to broadcast addresses (`SO_BROADCAST`).
+    pub fn set_broadcast(&self, broadcast: bool) -> io::Result<()> {
+        self.inner.sock_ref()?.set_broadcast(broadcast)
+    }
+
+    /// Returns `true` if sending to broadcast addresses is enabled.
+    pub fn broadcast(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.broadcast()
+    }
+
+    /// Joins the IPv4 multicast group `multiaddr` on the interface with the
+    /// address `interface`, or on the default interface if it is
+    /// [`Ipv4Addr::UNSPECIFIED`].
+    pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .join_multicast_v4(&multiaddr, &interface)
+    }
+
+    /// Leaves an IPv4 multicast group, see [`join_multicast_v4`].
+    ///
+    /// [`join_multicast_v4`]: Self::join_multicast_v4
+    pub fn leave_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .leave_multicast_v4(&multiaddr, &interface)
+    }
+
+    /// Joins the IPv6 multicast group `multiaddr` on the interface with the
+    /// index `interface`, or on the default interface if it is 0.
+    pub fn join_multicast_v6(&self, multiaddr: Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .join_multicast_v6(&multiaddr, interface)
+    }
+
+    /// Leaves an IPv6 multicast group, see [`join_multicast_v6`].
+    ///
+    /// [`join_multicast_v6`]: Self::join_multicast_v6
+    pub fn leave_multicast_v6(&self, multiaddr: Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .leave_multicast_v6(&multiaddr, interface)
+    }
+
+    /// Sets whether IPv4 multicast datagrams sent by the socket are looped
+    /// back to local sockets (`IP_MULTICAST_LOOP`).
+    pub fn set_multicast_loop_v4(&self, multicast_loop_v4: bool) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_multicast_loop_v4(multicast_loop_v4)
+    }
+
+    /// Returns `true` if IPv4 multicast datagrams are looped back.
+    pub fn multicast_loop_v4(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.multicast_loop_v4()
+    }
+
+    /// Sets the time-to-live of outgoing IPv4 multicast datagrams
+    /// (`IP_MULTICAST_TTL`).
+    pub fn set_multicast_ttl_v4(&self, multicast_ttl_v4: u32) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_multicast_ttl_v4(multicast_ttl_v4)
+    }
+
+    /// Returns the time-to-live of outgoing IPv4 multicast datagrams.
+    pub fn multicast_ttl_v4(&self) -> io::Result<u32> {
+        self.inner.sock_ref()?.multicast_ttl_v4()
+    }
+
+    /// Sets whether IPv6 multicast datagrams sent by the socket are looped
+    /// back to local sockets (`IPV6_MULTICAST_LOOP`).
+    pub fn set_multicast_loop_v6(&self, multicast_loop_v6: bool) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_multicast_loop_v6(multicast_loop_v6)
+    }
+
+    /// Returns `true` if IPv6 multicast datagrams are looped back.
+    pub fn multicast_loop_v6(&self) -> io::Result<bool> {
+        self.inner.sock_ref()?.multicast_loop_v6()
+    }
+
+    /// Sets the hop limit of outgoing IPv6 multicast datagrams
+    /// (`IPV6_MULTICAST_HOPS`).
+    pub fn set_multicast_hops_v6(&self, multicast_hops_v6: u32) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_multicast_hops_v6(multicast_hops_v6)
+    }
+
+    /// Returns the hop limit of outgoing IPv6 multicast datagrams.
+    pub fn multicast_hops_v6(&self) -> io::Result<u32> {
+        self.inner.sock_ref()?.multicast_hops_v6()
+    }
+
+    /// Sets the size of the socket's send buffer (`SO_SNDBUF`). The kernel
+    /// doubles the value, and enforces a minimum.
+    pub fn set_send_buffer_size(&self, send_buffer_size: usize) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_send_buffer_size(send_buffer_size)
+    }
+
+    /// Returns the size of the socket's send buffer.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.sock_ref()?.send_buffer_size()
+    }
+
+    /// Sets the size of the socket's receive buffer (`SO_RCVBUF`). The kernel
+    /// doubles the value, and enforces a minimum.
+    pub fn set_recv_buffer_size(&self, recv_buffer_size: usize) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_recv_buffer_size(recv_buffer_size)
+    }
+
+    /// Returns the size of the socket's receive buffer.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.sock_ref()?.recv_buffer_size()
     }
 
     /// Sends data on the socket to the given address. On success, returns the
@@ -110,7 +296,143 @@ impl UdpSocket {
         buf: T,
         socket_addr: SocketAddr,
     ) -> crate::BufResult<usize, T> {
-        self.inner.send_to(buf, socket_addr).await
+        self.inner.send_to(buf, socket_addr.into()).await
+    }
+
+    /// Sends a single datagram made of the initialized bytes of `bufs`, in
+    /// order, with a single `sendmsg`.
+    ///
+    /// The datagram goes to `socket_addr`, or to the connected address if it
+    /// is `None`. Returns the buffers and the number of bytes sent.
+    pub async fn send_msg<T: IoBuf>(
+        &self,
+        bufs: Vec<T>,
+        socket_addr: Option<SocketAddr>,
+    ) -> crate::BufResult<usize, Vec<T>> {
+        self.inner.send_msg(bufs, socket_addr).await
+    }
+
+    /// Like [`send_msg`], but also sending the control messages in `control`,
+    /// e.g. to choose the source address with `IP_PKTINFO`.
+    ///
+    /// Returns the buffers and the control messages.
+    ///
+    /// [`send_msg`]: Self::send_msg
+    pub async fn send_msg_with_control<T: IoBuf>(
+        &self,
+        bufs: Vec<T>,
+        socket_addr: Option<SocketAddr>,
+        control: ControlMessages,
+    ) -> crate::BufResult<usize, (Vec<T>, ControlMessages)> {
+        let (res, (bufs, control)) = self
+            .inner
+            .send_msg_with_control(bufs, socket_addr, control.into_vec())
+            .await;
+        (res, (bufs, ControlMessages::from_raw(control, 0)))
+    }
+
+    /// Receives a single datagram along with its origin and its control
+    /// messages, e.g. `IP_PKTINFO` or timestamps enabled on the socket.
+    ///
+    /// Control messages are received up to the capacity of `control`, see
+    /// [`ControlMessages::is_truncated`].
+    pub async fn recv_msg<T: IoBufMut>(
+        &self,
+        buf: T,
+        control: ControlMessages,
+    ) -> crate::BufResult<(usize, SocketAddr), (T, ControlMessages)> {
+        let (res, (buf, control)) = self.inner.recv_msg(buf, control.into_vec()).await;
+        let flags = res.as_ref().map_or(0, |meta| meta.flags);
+        let res = res.and_then(|meta| {
+            let socket_addr = meta
+                .socket_addr
+                .ok_or_else(|| io::Error::other("Could not get socket IP address"))?;
+            Ok((meta.len, socket_addr))
+        });
+        (res, (buf, ControlMessages::from_raw(control, flags)))
+    }
+
+    /// Sends `buf` as a burst of datagrams of `segment_size` bytes each, the
+    /// last one possibly shorter, with a single `sendmsg`.
+    ///
+    /// The datagrams go to `socket_addr`, or to the connected address if it
+    /// is `None`. Segmentation is done by the kernel or the network card (UDP
+    /// GSO), which requires Linux 4.18. At most 64 segments may be sent at
+    /// once.
+    pub async fn send_segments<T: IoBuf>(
+        &self,
+        buf: T,
+        segment_size: u16,
+        socket_addr: Option<SocketAddr>,
+    ) -> crate::BufResult<usize, T> {
+        let mut control = ControlMessages::new();
+        control.push(
+            libc::SOL_UDP,
+            libc::UDP_SEGMENT,
+            &segment_size.to_ne_bytes(),
+        );
+
+        let (res, (mut bufs, _)) = self
+            .inner
+            .send_msg_with_control(vec![buf], socket_addr, control.into_vec())
+            .await;
+        (res, bufs.pop().unwrap())
+    }
+
+    /// Enables or disables UDP GRO, which lets the kernel coalesce datagrams
+    /// of equal size from the same origin, see [`recv_gro`].
+    ///
+    /// [`recv_gro`]: Self::recv_gro
+    pub fn set_gro(&self, enabled: bool) -> io::Result<()> {
+        self.inner
+            .setsockopt(libc::SOL_UDP, libc::UDP_GRO, enabled as libc::c_int)
+    }
+
+    /// Receives datagrams possibly coalesced by the kernel, once GRO was
+    /// enabled with [`set_gro`].
+    ///
+    /// On success, returns the number of bytes read, the origin, and the size
+    /// of the coalesced datagrams. Every datagram has that size, except the
+    /// last one, which may be shorter. Without coalescing, the size is the
+    /// number of bytes read.
+    ///
+    /// [`set_gro`]: Self::set_gro
+    pub async fn recv_gro<T: IoBufMut>(
+        &self,
+        buf: T,
+    ) -> crate::BufResult<(usize, SocketAddr, usize), T> {
+        let space = ControlMessages::space(std::mem::size_of::<libc::c_int>());
+        let control = ControlMessages::with_capacity(space);
+
+        let (res, (buf, control)) = self.recv_msg(buf, control).await;
+        let res = res.map(|(n, socket_addr)| {
+            let segment_size = control
+                .iter()
+                .find(|msg| msg.level() == libc::SOL_UDP && msg.ty() == libc::UDP_GRO)
+                .and_then(|msg| msg.data().try_into().ok())
+                .map_or(n, |size| libc::c_int::from_ne_bytes(size) as usize);
+            (n, socket_addr, segment_size)
+        });
+        (res, buf)
+    }
+
+    /// Like [`send_to`], but without copying the data into the socket buffer.
+    ///
+    /// The buffer is returned once the network stack released it. Zero-copy
+    /// send requires Linux 6.1.
+    ///
+    /// There is no variant taking a registered buffer: zero-copy `sendmsg`,
+    /// which carries the destination, does not accept one. Connect the socket
+    /// and use [`send_zc_fixed`] instead.
+    ///
+    /// [`send_to`]: Self::send_to
+    /// [`send_zc_fixed`]: Self::send_zc_fixed
+    pub async fn send_to_zc<T: IoBuf>(
+        &self,
+        buf: T,
+        socket_addr: SocketAddr,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.send_to_zc(buf, socket_addr).await
     }
 
     /// Receives a single datagram message on the socket. On success, returns
@@ -119,6 +441,34 @@ impl UdpSocket {
         self.inner.recv_from(buf).await
     }
 
+    /// Receives a single datagram into a buffer selected by the kernel from
+    /// `pool`.
+    ///
+    /// An empty datagram is returned as an empty buffer, which holds no buffer
+    /// of the pool. Datagrams larger than the buffers of the pool are
+    /// truncated. Fails with `ENOBUFS` if the pool has no free buffer when a
+    /// datagram arrives.
+    pub async fn recv_pooled(&self, pool: &BufPool) -> io::Result<ProvidedBuf> {
+        self.inner.recv_pooled(pool).await
+    }
+
+    /// Returns a stream of datagrams received into buffers selected by the
+    /// kernel from `pool`, along with their source addresses.
+    ///
+    /// A single multishot `recvmsg` stays armed while datagrams arrive,
+    /// instead of submitting one receive per datagram. The kernel writes a
+    /// header and the source address before each datagram, which takes 44
+    /// bytes of every buffer, and truncates datagrams not fitting in the rest.
+    ///
+    /// The stream ends after an error. When the pool has no free buffer, it
+    /// yields `ENOBUFS` and receiving resumes on the next poll. Multishot
+    /// `recvmsg` requires Linux 6.0.
+    pub fn recv_msg_multishot(&self, pool: &BufPool) -> RecvMsgStream {
+        RecvMsgStream {
+            inner: self.inner.recv_multi(pool, true),
+        }
+    }
+
     /// Read a packet of data from the socket into the buffer, returning the original buffer and
     /// quantity of data read.
     pub async fn read<T: IoBufMut>(&self, buf: T) -> crate::BufResult<usize, T> {
@@ -130,4 +480,101 @@ impl UdpSocket {
     pub async fn write<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
         self.inner.write(buf).await
     }
+
+    /// Like [`read`], but receives with `flags`.
+    ///
+    /// With [`RecvFlags::PEEK`], the data stays queued and is returned again
+    /// by the next receive.
+    ///
+    /// [`read`]: Self::read
+    pub async fn recv_with_flags<T: IoBufMut>(
+        &self,
+        buf: T,
+        flags: RecvFlags,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.recv(buf, flags.bits()).await
+    }
+
+    /// Like [`write`], but sends with `flags`.
+    ///
+    /// With [`SendFlags::NOSIGNAL`], sending on a connection closed by the peer
+    /// fails with `EPIPE` instead of raising `SIGPIPE`.
+    ///
+    /// [`write`]: Self::write
+    pub async fn send_with_flags<T: IoBuf>(
+        &self,
+        buf: T,
+        flags: SendFlags,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.send(buf, flags.bits()).await
+    }
+
+    /// Like [`read`], but using a buffer registered with the kernel.
+    ///
+    /// [`read`]: Self::read
+    pub async fn read_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        self.inner.read_fixed(buf).await
+    }
+
+    /// Like [`write`], but using a buffer registered with the kernel.
+    ///
+    /// [`write`]: Self::write
+    pub async fn write_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        self.inner.write_fixed(buf).await
+    }
+
+    /// Sends a datagram to the connected peer without copying it into the
+    /// socket buffer, from a buffer registered with the kernel.
+    ///
+    /// The buffer is returned once the network stack released it. Zero-copy
+    /// send requires Linux 6.0.
+    pub async fn send_zc_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        self.inner.send_zc_fixed(buf).await
+    }
+
+    /// Closes the socket.
+    ///
+    /// The method completes once the close operation has completed,
+    /// guaranteeing that resources associated with the socket have been
+    /// released.
+    ///
+    /// The close is submitted once no operation or stream created from the
+    /// socket still uses its descriptor.
+    ///
+    /// If `close` is not called before dropping the socket, it is closed in the
+    /// background, but there is no guarantee as to **when** the close
+    /// operation will complete.
+    pub async fn close(self) -> io::Result<()> {
+        self.inner.close().await
+    }
+}
+
+/// A stream of datagrams received by [`UdpSocket::recv_msg_multishot`].
+pub struct RecvMsgStream {
+    inner: driver::RecvStream,
+}
+
+impl RecvMsgStream {
+    /// Stops receiving.
+    ///
+    /// Datagrams the kernel already received are still yielded, then the
+    /// stream ends.
+    pub fn cancel(&mut self) {
+        self.inner.cancel();
+    }
+}
+
+impl Stream for RecvMsgStream {
+    type Item = io::Result<(ProvidedBuf, SocketAddr)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().inner.poll_next(cx).map(|item| {
+            item.map(|result| {
+                let (buf, addr) = result?;
+                let addr =
+                    addr.ok_or_else(|| io::Error::other("Could not get socket IP address"))?;
+                Ok((buf, addr))
+            })
+        })
+    }
 }
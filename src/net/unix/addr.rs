@@ -0,0 +1,105 @@
+use socket2::SockAddr;
+use std::{ffi::OsStr, fmt, io, mem, os::unix::ffi::OsStrExt, path::Path};
+
+/// The address of a Unix socket.
+///
+/// An address is either a path in the filesystem, a name in the Linux
+/// abstract namespace, or unnamed, as for sockets that were never bound.
+/// Abstract names are not backed by a file: they need no cleanup, and
+/// disappear once the last socket bound to them is closed.
+///
+/// # Examples
+///
+/// ```
+/// use async_uring::net::UnixSocketAddr;
+///
+/// let addr = UnixSocketAddr::from_abstract_name(b"journal").unwrap();
+/// assert_eq!(addr.as_abstract_name(), Some(&b"journal"[..]));
+/// assert_eq!(addr.as_pathname(), None);
+/// ```
+#[derive(Clone)]
+pub struct UnixSocketAddr {
+    addr: SockAddr,
+}
+
+impl UnixSocketAddr {
+    /// Creates an address from a filesystem path.
+    pub fn from_pathname<P: AsRef<Path>>(path: P) -> io::Result<UnixSocketAddr> {
+        let path = path.as_ref().as_os_str().as_bytes();
+        if matches!(path.first(), None | Some(0)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "path must not be empty or start with a nul byte",
+            ));
+        }
+
+        UnixSocketAddr::from_bytes(path)
+    }
+
+    /// Creates an address in the abstract namespace. The name may contain
+    /// any byte, including nul bytes.
+    pub fn from_abstract_name<N: AsRef<[u8]>>(name: N) -> io::Result<UnixSocketAddr> {
+        let mut path = vec![0];
+        path.extend_from_slice(name.as_ref());
+        UnixSocketAddr::from_bytes(&path)
+    }
+
+    fn from_bytes(path: &[u8]) -> io::Result<UnixSocketAddr> {
+        let addr = SockAddr::unix(OsStr::from_bytes(path))?;
+        Ok(UnixSocketAddr { addr })
+    }
+
+    /// Returns the path of the address, if it is a filesystem path.
+    pub fn as_pathname(&self) -> Option<&Path> {
+        match self.sun_path() {
+            [] | [0, ..] => None,
+            path => {
+                // The path is nul-terminated, unless it fills `sun_path`.
+                let len = path.iter().position(|&b| b == 0).unwrap_or(path.len());
+                Some(Path::new(OsStr::from_bytes(&path[..len])))
+            }
+        }
+    }
+
+    /// Returns the name of the address, if it is in the abstract namespace.
+    pub fn as_abstract_name(&self) -> Option<&[u8]> {
+        match self.sun_path() {
+            [0, name @ ..] => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the address is unnamed.
+    pub fn is_unnamed(&self) -> bool {
+        self.sun_path().is_empty()
+    }
+
+    /// Returns the bytes of `sun_path` set by the address length.
+    fn sun_path(&self) -> &[u8] {
+        let offset = mem::size_of::<libc::sa_family_t>();
+        let len = (self.addr.len() as usize).saturating_sub(offset);
+
+        // Safety: the storage holds at least `len` bytes past the family.
+        unsafe { std::slice::from_raw_parts((self.addr.as_ptr() as *const u8).add(offset), len) }
+    }
+
+    pub(crate) fn from_sock_addr(addr: SockAddr) -> UnixSocketAddr {
+        UnixSocketAddr { addr }
+    }
+
+    pub(crate) fn as_sock_addr(&self) -> &SockAddr {
+        &self.addr
+    }
+}
+
+impl fmt::Debug for UnixSocketAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = self.as_pathname() {
+            write!(f, "{:?} (pathname)", path)
+        } else if let Some(name) = self.as_abstract_name() {
+            write!(f, "\"{}\" (abstract)", name.escape_ascii())
+        } else {
+            write!(f, "(unnamed)")
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use super::UnixSocketAddr;
+use crate::{
+    buf::{IoBuf, IoBufMut},
+    driver::Socket,
+};
+use std::{io, path::Path};
+
+/// A Unix datagram socket.
+///
+/// Like a UDP socket, a datagram socket can either exchange datagrams with
+/// many peers, using [`send_to`] and [`recv_from`], or be connected to a
+/// single peer and use [`write`] and [`read`]. Unlike UDP, delivery is
+/// reliable and ordered.
+///
+/// # Examples
+///
+/// Sending a message to a journald-style socket in the abstract namespace:
+///
+/// ```no_run
+/// use async_uring::net::{UnixDatagram, UnixSocketAddr};
+///
+/// fn main() -> std::io::Result<()> {
+///     async_uring::start(async {
+///         let socket = UnixDatagram::unbound()?;
+///         let addr = UnixSocketAddr::from_abstract_name(b"agent.log")?;
+///
+///         let (res, _) = socket.send_to(&b"MESSAGE=started"[..], &addr).await;
+///         res?;
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+///
+/// [`send_to`]: UnixDatagram::send_to
+/// [`recv_from`]: UnixDatagram::recv_from
+/// [`write`]: UnixDatagram::write
+/// [`read`]: UnixDatagram::read
+pub struct UnixDatagram {
+    inner: Socket,
+}
+
+impl UnixDatagram {
+    /// Creates a new datagram socket bound to the specified file path, which
+    /// must not exist yet.
+    pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<UnixDatagram> {
+        UnixDatagram::bind_addr(&UnixSocketAddr::from_pathname(path)?)
+    }
+
+    /// Creates a new datagram socket bound to `addr`, which may be in the
+    /// abstract namespace.
+    pub fn bind_addr(addr: &UnixSocketAddr) -> io::Result<UnixDatagram> {
+        let socket = Socket::bind_unix(addr.as_sock_addr(), libc::SOCK_DGRAM)?;
+        Ok(UnixDatagram { inner: socket })
+    }
+
+    /// Creates a new datagram socket which is not bound to any address.
+    ///
+    /// Such a socket can send datagrams, but peers cannot reply to it.
+    pub fn unbound() -> io::Result<UnixDatagram> {
+        let socket = Socket::new_unix(libc::SOCK_DGRAM)?;
+        Ok(UnixDatagram { inner: socket })
+    }
+
+    /// Creates an unnamed pair of connected datagram sockets.
+    pub fn pair() -> io::Result<(UnixDatagram, UnixDatagram)> {
+        let (a, b) = Socket::pair_unix(libc::SOCK_DGRAM)?;
+        Ok((UnixDatagram { inner: a }, UnixDatagram { inner: b }))
+    }
+
+    /// Connects the socket to the specified file path, so that [`write`] and
+    /// [`read`] exchange datagrams with that peer only.
+    ///
+    /// [`write`]: UnixDatagram::write
+    /// [`read`]: UnixDatagram::read
+    pub async fn connect<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.connect_addr(&UnixSocketAddr::from_pathname(path)?)
+            .await
+    }
+
+    /// Connects the socket to `addr`, which may be in the abstract namespace.
+    pub async fn connect_addr(&self, addr: &UnixSocketAddr) -> io::Result<()> {
+        self.inner.connect(addr.as_sock_addr().clone()).await
+    }
+
+    /// Returns the local address of the socket.
+    pub fn local_addr(&self) -> io::Result<UnixSocketAddr> {
+        self.inner
+            .local_addr_unix()
+            .map(UnixSocketAddr::from_sock_addr)
+    }
+
+    /// Returns the address of the peer the socket is connected to.
+    pub fn peer_addr(&self) -> io::Result<UnixSocketAddr> {
+        self.inner
+            .peer_addr_unix()
+            .map(UnixSocketAddr::from_sock_addr)
+    }
+
+    /// Sets the size of the socket's send buffer (`SO_SNDBUF`). The kernel
+    /// doubles the value, and enforces a minimum.
+    pub fn set_send_buffer_size(&self, send_buffer_size: usize) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_send_buffer_size(send_buffer_size)
+    }
+
+    /// Returns the size of the socket's send buffer.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.sock_ref()?.send_buffer_size()
+    }
+
+    /// Sets the size of the socket's receive buffer (`SO_RCVBUF`). The kernel
+    /// doubles the value, and enforces a minimum.
+    pub fn set_recv_buffer_size(&self, recv_buffer_size: usize) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_recv_buffer_size(recv_buffer_size)
+    }
+
+    /// Returns the size of the socket's receive buffer.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.sock_ref()?.recv_buffer_size()
+    }
+
+    /// Sends a datagram to `addr`. On success, returns the number of bytes
+    /// written.
+    pub async fn send_to<T: IoBuf>(
+        &self,
+        buf: T,
+        addr: &UnixSocketAddr,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.send_to(buf, addr.as_sock_addr().clone()).await
+    }
+
+    /// Receives a datagram into the buffer, returning the original buffer,
+    /// the quantity of data read and the address of the sender.
+    ///
+    /// The part of a datagram that does not fit in the buffer is discarded.
+    pub async fn recv_from<T: IoBufMut>(
+        &self,
+        buf: T,
+    ) -> crate::BufResult<(usize, UnixSocketAddr), T> {
+        let (res, buf) = self.inner.recv_from_unix(buf).await;
+        let res = res.map(|(n, addr)| (n, UnixSocketAddr::from_sock_addr(addr)));
+        (res, buf)
+    }
+
+    /// Read a datagram from the connected peer into the buffer, returning the
+    /// original buffer and quantity of data read.
+    pub async fn read<T: IoBufMut>(&self, buf: T) -> crate::BufResult<usize, T> {
+        self.inner.read(buf).await
+    }
+
+    /// Write a datagram to the connected peer from the buffer, returning the
+    /// original buffer and quantity of data written.
+    pub async fn write<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
+        self.inner.write(buf).await
+    }
+
+    /// Closes the socket.
+    ///
+    /// The method completes once the close operation has completed,
+    /// guaranteeing that resources associated with the socket have been
+    /// released.
+    ///
+    /// The close is submitted once no operation or stream created from the
+    /// socket still uses its descriptor.
+    ///
+    /// If `close` is not called before dropping the socket, it is closed in the
+    /// background, but there is no guarantee as to **when** the close
+    /// operation will complete.
+    pub async fn close(self) -> io::Result<()> {
+        self.inner.close().await
+    }
+}
@@ -1,6 +1,12 @@
-use super::UnixStream;
-use crate::driver::Socket;
-use std::{io, path::Path};
+use super::{UnixSocketAddr, UnixStream};
+use crate::driver::{self, Socket};
+use futures_lite::Stream;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 /// A Unix socket server, listening for connections.
 ///
@@ -23,7 +29,7 @@ use std::{io, path::Path};
 ///     let rx_fut = listener.accept();
 ///
 ///     let (tx, rx) = futures_lite::future::zip(tx_fut, rx_fut).await;
-///     let (tx, rx) = (tx.unwrap(), rx.unwrap());
+///     let (tx, (rx, _)) = (tx.unwrap(), rx.unwrap());
 ///
 ///     tx.write(b"test" as &'static [u8]).await.0.unwrap();
 ///
@@ -31,32 +37,160 @@ use std::{io, path::Path};
 ///
 ///     assert_eq!(buf, b"test");
 /// });
-/// std::fs::remove_file(&sock_file);
-///
 /// ```
 pub struct UnixListener {
     inner: Socket,
+
+    /// Path of the socket file, for filesystem addresses.
+    path: Option<PathBuf>,
+
+    remove_on_drop: bool,
 }
 
 impl UnixListener {
     /// Creates a new UnixListener, which will be bound to the specified file path.
-    /// The file path cannnot yet exist, and will be cleaned up upon dropping `UnixListener`
+    /// The file path cannnot yet exist, and will be cleaned up upon dropping `UnixListener`,
+    /// unless disabled with [`set_remove_on_drop`].
+    ///
+    /// [`set_remove_on_drop`]: UnixListener::set_remove_on_drop
     pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<UnixListener> {
-        let socket = Socket::bind_unix(path, libc::SOCK_STREAM)?;
+        UnixListener::bind_addr(&UnixSocketAddr::from_pathname(path)?)
+    }
+
+    /// Creates a new UnixListener bound to `addr`, which may be in the
+    /// abstract namespace. As with [`bind`], a socket file is removed on
+    /// drop.
+    ///
+    /// [`bind`]: UnixListener::bind
+    pub fn bind_addr(addr: &UnixSocketAddr) -> io::Result<UnixListener> {
+        UnixListener::bind_type(addr, libc::SOCK_STREAM)
+    }
+
+    /// Creates a new UnixListener for `SOCK_SEQPACKET` connections, bound to
+    /// `addr`.
+    ///
+    /// The accepted streams are connection-oriented like stream sockets, but
+    /// preserve message boundaries: each write sends one message, and each
+    /// read receives at most one message. See
+    /// [`UnixStream::connect_seqpacket`].
+    pub fn bind_seqpacket(addr: &UnixSocketAddr) -> io::Result<UnixListener> {
+        UnixListener::bind_type(addr, libc::SOCK_SEQPACKET)
+    }
+
+    fn bind_type(addr: &UnixSocketAddr, socket_type: libc::c_int) -> io::Result<UnixListener> {
+        let socket = Socket::bind_unix(addr.as_sock_addr(), socket_type)?;
         socket.listen(1024)?;
-        Ok(UnixListener { inner: socket })
+        Ok(UnixListener {
+            inner: socket,
+            path: addr.as_pathname().map(Path::to_path_buf),
+            remove_on_drop: true,
+        })
+    }
+
+    /// Sets whether the socket file is removed when the listener is dropped,
+    /// which is the default.
+    ///
+    /// Disable it to keep the path bound, for instance when the listener is
+    /// handed over to another process.
+    pub fn set_remove_on_drop(&mut self, remove: bool) {
+        self.remove_on_drop = remove;
+    }
+
+    /// Returns the local address the listener is bound to.
+    pub fn local_addr(&self) -> io::Result<UnixSocketAddr> {
+        self.inner
+            .local_addr_unix()
+            .map(UnixSocketAddr::from_sock_addr)
     }
 
     /// Accepts a new incoming connection from this listener.
     ///
     /// This function will yield once a new Unix domain socket connection
     /// is established. When established, the corresponding [`UnixStream`] and
-    /// will be returned.
+    /// the remote peer's address will be returned. The address is usually
+    /// unnamed, as clients rarely bind their sockets.
     ///
     /// [`UnixStream`]: struct@crate::net::UnixStream
-    pub async fn accept(&self) -> io::Result<UnixStream> {
-        let (socket, _) = self.inner.accept().await?;
+    pub async fn accept(&self) -> io::Result<(UnixStream, UnixSocketAddr)> {
+        let (socket, addr) = self.inner.accept(false).await?;
         let stream = UnixStream { inner: socket };
-        Ok(stream)
+        Ok((stream, UnixSocketAddr::from_sock_addr(addr)))
+    }
+
+    /// Returns a stream of incoming connections.
+    ///
+    /// A single multishot accept keeps accepting connections until the stream
+    /// is dropped, instead of submitting one operation per connection.
+    /// Multishot accept requires Linux 5.19.
+    ///
+    /// Connections accepted by the kernel after the stream was dropped are
+    /// closed.
+    pub fn incoming(&self) -> UnixIncoming {
+        UnixIncoming {
+            inner: self.inner.incoming(),
+        }
+    }
+
+    /// Closes the listener.
+    ///
+    /// The method completes once the close operation has completed,
+    /// guaranteeing that resources associated with the listener have been
+    /// released.
+    ///
+    /// The close is submitted once no operation or stream created from the
+    /// listener still uses its descriptor.
+    ///
+    /// If `close` is not called before dropping the listener, it is closed in the
+    /// background, but there is no guarantee as to **when** the close
+    /// operation will complete.
+    pub async fn close(self) -> io::Result<()> {
+        let inner = self.inner.clone();
+        // Removes the socket file.
+        drop(self);
+        inner.close().await
+    }
+}
+
+impl Drop for UnixListener {
+    fn drop(&mut self) {
+        if !self.remove_on_drop {
+            return;
+        }
+
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// A stream of connections accepted by a [`UnixListener`].
+///
+/// Created by [`UnixListener::incoming`]. The stream only ends once cancelled, and
+/// may be polled again after yielding an error.
+pub struct UnixIncoming {
+    inner: driver::Incoming,
+}
+
+impl UnixIncoming {
+    /// Stops accepting connections.
+    ///
+    /// Connections the kernel already accepted are still yielded, then the
+    /// stream ends. Later connections wait in the listen queue.
+    pub fn cancel(&mut self) {
+        self.inner.cancel();
+    }
+}
+
+impl Stream for UnixIncoming {
+    type Item = io::Result<UnixStream>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().inner.poll_next(cx).map(|item| {
+            item.map(|result| {
+                result.map(|fd| UnixStream {
+                    inner: Socket::from_shared_fd(fd),
+                })
+            })
+        })
     }
 }
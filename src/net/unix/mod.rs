@@ -1,5 +1,14 @@
+mod addr;
+pub use addr::UnixSocketAddr;
+
+mod datagram;
+pub use datagram::UnixDatagram;
+
 mod listener;
-pub use listener::UnixListener;
+pub use listener::{UnixIncoming, UnixListener};
 
 mod stream;
 pub use stream::UnixStream;
+
+mod ucred;
+pub use ucred::UCred;
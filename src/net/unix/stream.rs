@@ -1,9 +1,10 @@
 use crate::{
-    buf::{IoBuf, IoBufMut},
+    buf::{fixed::FixedBuf, BufPool, IoBuf, IoBufMut, ProvidedBuf},
+    compat::Compat,
     driver::Socket,
+    net::{ControlMessages, RecvFlags, SendFlags, UCred, UnixSocketAddr},
 };
-use socket2::SockAddr;
-use std::{io, path::Path};
+use std::{io, net::Shutdown, path::Path, time::Duration};
 
 /// A Unix stream between two local sockets on a Unix OS.
 ///
@@ -42,12 +43,104 @@ impl UnixStream {
     /// `UnixListener` or equivalent listening on the corresponding Unix domain socket
     /// to successfully connect and return a `UnixStream`.
     pub async fn connect<P: AsRef<Path>>(path: P) -> io::Result<UnixStream> {
-        let socket = Socket::new_unix(libc::SOCK_STREAM)?;
-        socket.connect(SockAddr::unix(path)?).await?;
+        UnixStream::connect_addr(&UnixSocketAddr::from_pathname(path)?).await
+    }
+
+    /// Opens a Unix connection to `addr`, which may be in the abstract
+    /// namespace.
+    pub async fn connect_addr(addr: &UnixSocketAddr) -> io::Result<UnixStream> {
+        UnixStream::connect_type(addr, libc::SOCK_STREAM).await
+    }
+
+    /// Opens a `SOCK_SEQPACKET` connection to `addr`, see
+    /// [`UnixListener::bind_seqpacket`].
+    ///
+    /// The stream preserves message boundaries: each write sends one message,
+    /// and each read receives at most one message, discarding the part of it
+    /// that does not fit in the buffer.
+    ///
+    /// [`UnixListener::bind_seqpacket`]: crate::net::UnixListener::bind_seqpacket
+    pub async fn connect_seqpacket(addr: &UnixSocketAddr) -> io::Result<UnixStream> {
+        UnixStream::connect_type(addr, libc::SOCK_SEQPACKET).await
+    }
+
+    async fn connect_type(
+        addr: &UnixSocketAddr,
+        socket_type: libc::c_int,
+    ) -> io::Result<UnixStream> {
+        let socket = Socket::new_unix(socket_type)?;
+        socket.connect(addr.as_sock_addr().clone()).await?;
         let unix_stream = UnixStream { inner: socket };
         Ok(unix_stream)
     }
 
+    /// Creates an unnamed pair of connected streams.
+    pub fn pair() -> io::Result<(UnixStream, UnixStream)> {
+        UnixStream::pair_type(libc::SOCK_STREAM)
+    }
+
+    /// Creates an unnamed pair of connected `SOCK_SEQPACKET` streams, see
+    /// [`connect_seqpacket`].
+    ///
+    /// [`connect_seqpacket`]: UnixStream::connect_seqpacket
+    pub fn pair_seqpacket() -> io::Result<(UnixStream, UnixStream)> {
+        UnixStream::pair_type(libc::SOCK_SEQPACKET)
+    }
+
+    fn pair_type(socket_type: libc::c_int) -> io::Result<(UnixStream, UnixStream)> {
+        let (a, b) = Socket::pair_unix(socket_type)?;
+        Ok((UnixStream { inner: a }, UnixStream { inner: b }))
+    }
+
+    /// Returns the local address of the stream, which is usually unnamed.
+    pub fn local_addr(&self) -> io::Result<UnixSocketAddr> {
+        self.inner
+            .local_addr_unix()
+            .map(UnixSocketAddr::from_sock_addr)
+    }
+
+    /// Returns the address of the remote peer.
+    pub fn peer_addr(&self) -> io::Result<UnixSocketAddr> {
+        self.inner
+            .peer_addr_unix()
+            .map(UnixSocketAddr::from_sock_addr)
+    }
+
+    /// Returns the credentials of the peer (`SO_PEERCRED`), which a server
+    /// can use to authorize local clients.
+    pub fn peer_cred(&self) -> io::Result<UCred> {
+        let cred = self
+            .inner
+            .getsockopt::<libc::ucred>(libc::SOL_SOCKET, libc::SO_PEERCRED)?;
+        Ok(UCred::from_raw(cred))
+    }
+
+    /// Sets the size of the socket's send buffer (`SO_SNDBUF`). The kernel
+    /// doubles the value, and enforces a minimum.
+    pub fn set_send_buffer_size(&self, send_buffer_size: usize) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_send_buffer_size(send_buffer_size)
+    }
+
+    /// Returns the size of the socket's send buffer.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.sock_ref()?.send_buffer_size()
+    }
+
+    /// Sets the size of the socket's receive buffer (`SO_RCVBUF`). The kernel
+    /// doubles the value, and enforces a minimum.
+    pub fn set_recv_buffer_size(&self, recv_buffer_size: usize) -> io::Result<()> {
+        self.inner
+            .sock_ref()?
+            .set_recv_buffer_size(recv_buffer_size)
+    }
+
+    /// Returns the size of the socket's receive buffer.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.sock_ref()?.recv_buffer_size()
+    }
+
     /// Read some data from the stream into the buffer, returning the original buffer and
     /// quantity of data read.
     pub async fn read<T: IoBufMut>(&self, buf: T) -> crate::BufResult<usize, T> {
@@ -59,4 +152,164 @@ impl UnixStream {
     pub async fn write<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
         self.inner.write(buf).await
     }
+
+    /// Like [`read`], but receives with `flags`.
+    ///
+    /// With [`RecvFlags::PEEK`], the data stays queued and is returned again
+    /// by the next receive.
+    ///
+    /// [`read`]: Self::read
+    pub async fn recv_with_flags<T: IoBufMut>(
+        &self,
+        buf: T,
+        flags: RecvFlags,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.recv(buf, flags.bits()).await
+    }
+
+    /// Write the initialized bytes of `bufs`, in order, to the stream with a
+    /// single `writev`, returning the buffers and the quantity of data
+    /// written.
+    ///
+    /// As with [`write`], only a prefix of the data may be written.
+    ///
+    /// [`write`]: Self::write
+    pub async fn writev<T: IoBuf>(&self, bufs: Vec<T>) -> crate::BufResult<usize, Vec<T>> {
+        self.inner.writev(bufs).await
+    }
+
+    /// Like [`writev`], but also sending the control messages in `control`,
+    /// e.g. descriptors with [`ControlMessages::push_scm_rights`].
+    ///
+    /// Returns the buffers and the control messages.
+    ///
+    /// [`writev`]: Self::writev
+    pub async fn send_msg_with_control<T: IoBuf>(
+        &self,
+        bufs: Vec<T>,
+        control: ControlMessages,
+    ) -> crate::BufResult<usize, (Vec<T>, ControlMessages)> {
+        let (res, (bufs, control)) = self
+            .inner
+            .send_msg_with_control(bufs, None, control.into_vec())
+            .await;
+        (res, (bufs, ControlMessages::from_raw(control, 0)))
+    }
+
+    /// Like [`read`], but also receiving control messages, e.g. descriptors
+    /// passed by the peer.
+    ///
+    /// Control messages are received up to the capacity of `control`, see
+    /// [`ControlMessages::is_truncated`].
+    ///
+    /// [`read`]: Self::read
+    pub async fn recv_msg<T: IoBufMut>(
+        &self,
+        buf: T,
+        control: ControlMessages,
+    ) -> crate::BufResult<usize, (T, ControlMessages)> {
+        let (res, (buf, control)) = self.inner.recv_msg(buf, control.into_vec()).await;
+        let flags = res.as_ref().map_or(0, |meta| meta.flags);
+        (
+            res.map(|meta| meta.len),
+            (buf, ControlMessages::from_raw(control, flags)),
+        )
+    }
+
+    /// Like [`write`], but sends with `flags`.
+    ///
+    /// With [`SendFlags::NOSIGNAL`], sending on a connection closed by the peer
+    /// fails with `EPIPE` instead of raising `SIGPIPE`.
+    ///
+    /// [`write`]: Self::write
+    pub async fn send_with_flags<T: IoBuf>(
+        &self,
+        buf: T,
+        flags: SendFlags,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.send(buf, flags.bits()).await
+    }
+
+    /// Like [`read`], but using a buffer registered with the kernel.
+    ///
+    /// [`read`]: Self::read
+    pub async fn read_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        self.inner.read_fixed(buf).await
+    }
+
+    /// Read some data from the stream into a buffer selected by the kernel
+    /// from `pool`.
+    ///
+    /// No buffer is held while waiting for data, which makes this suited for
+    /// many mostly idle connections. Returns `None` at the end of the stream.
+    /// Fails with `ENOBUFS` if the pool has no free buffer when the read
+    /// completes, including at the end of the stream.
+    pub async fn read_pooled(&self, pool: &BufPool) -> io::Result<Option<ProvidedBuf>> {
+        self.inner.read_pooled(pool).await
+    }
+
+    /// Like [`write`], but using a buffer registered with the kernel.
+    ///
+    /// [`write`]: Self::write
+    pub async fn write_fixed(&self, buf: FixedBuf) -> crate::BufResult<usize, FixedBuf> {
+        self.inner.write_fixed(buf).await
+    }
+
+    /// Like [`read`], but fails with `ErrorKind::TimedOut` if no data arrives
+    /// within `timeout`.
+    ///
+    /// The deadline is enforced by the kernel through a linked timeout, which
+    /// cancels the read. The buffer is returned in either case.
+    ///
+    /// [`read`]: Self::read
+    pub async fn read_with_timeout<T: IoBufMut>(
+        &self,
+        buf: T,
+        timeout: Duration,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.read_with_timeout(buf, timeout).await
+    }
+
+    /// Like [`write`], but fails with `ErrorKind::TimedOut` if the data
+    /// cannot be written within `timeout`.
+    ///
+    /// [`write`]: Self::write
+    pub async fn write_with_timeout<T: IoBuf>(
+        &self,
+        buf: T,
+        timeout: Duration,
+    ) -> crate::BufResult<usize, T> {
+        self.inner.write_with_timeout(buf, timeout).await
+    }
+
+    /// Shuts down the read half, write half, or both halves of the stream.
+    ///
+    /// Shutting down the write half makes the peer read the end of the
+    /// stream, while this side can still read.
+    pub async fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how).await
+    }
+
+    /// Closes the stream.
+    ///
+    /// The method completes once the close operation has completed,
+    /// guaranteeing that resources associated with the stream have been
+    /// released.
+    ///
+    /// The close is submitted once no operation or stream created from the
+    /// stream still uses its descriptor.
+    ///
+    /// If `close` is not called before dropping the stream, it is closed in the
+    /// background, but there is no guarantee as to **when** the close
+    /// operation will complete.
+    pub async fn close(self) -> io::Result<()> {
+        self.inner.close().await
+    }
+
+    /// Wraps the stream to implement the `futures-io` traits, see [`Compat`].
+    ///
+    /// [`Compat`]: crate::compat::Compat
+    pub fn compat(self) -> Compat<UnixStream> {
+        Compat::new(self, |stream| stream.inner.shared_fd(), false)
+    }
 }
@@ -0,0 +1,37 @@
+/// Credentials of the peer of a Unix socket, see [`UnixStream::peer_cred`].
+///
+/// The credentials are those of the process that connected the socket, or
+/// created the pair, at that time.
+///
+/// [`UnixStream::peer_cred`]: crate::net::UnixStream::peer_cred
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UCred {
+    pid: libc::pid_t,
+    uid: libc::uid_t,
+    gid: libc::gid_t,
+}
+
+impl UCred {
+    pub(crate) fn from_raw(cred: libc::ucred) -> UCred {
+        UCred {
+            pid: cred.pid,
+            uid: cred.uid,
+            gid: cred.gid,
+        }
+    }
+
+    /// Returns the process ID of the peer.
+    pub fn pid(&self) -> libc::pid_t {
+        self.pid
+    }
+
+    /// Returns the user ID of the peer.
+    pub fn uid(&self) -> libc::uid_t {
+        self.uid
+    }
+
+    /// Returns the group ID of the peer.
+    pub fn gid(&self) -> libc::gid_t {
+        self.gid
+    }
+}
@@ -0,0 +1,226 @@
+use crate::runtime::Runtime;
+use io_uring::IoUring;
+use std::{io, os::unix::io::RawFd, time::Duration};
+
+/// Builds a [`Runtime`] with custom ring configuration.
+///
+/// Methods can be chained in order to set the configuration values. The
+/// `Runtime` is constructed by calling [`build`].
+///
+/// By default the ring is created with 256 submission queue entries and no
+/// setup flags, which matches the runtime used by [`start`].
+///
+/// [`build`]: Builder::build
+/// [`start`]: crate::start
+///
+/// # Examples
+///
+/// Create a large ring with a kernel-side submission polling thread:
+///
+/// ```no_run
+/// use async_uring::Builder;
+/// use std::time::Duration;
+///
+/// let mut rt = Builder::new()
+///     .entries(4096)
+///     .sqpoll(Duration::from_millis(2000))
+///     .sqpoll_cpu(0)
+///     .build()
+///     .unwrap();
+///
+/// rt.block_on(async {
+///     // ...
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder {
+    entries: u32,
+    cq_entries: Option<u32>,
+    sqpoll_idle: Option<Duration>,
+    sqpoll_cpu: Option<u32>,
+    iopoll: bool,
+    coop_taskrun: bool,
+    single_issuer: bool,
+    attach_wq: Option<RawFd>,
+    pub(crate) fixed_files: u32,
+    pub(crate) shutdown_timeout: Duration,
+}
+
+impl Builder {
+    /// Creates a blank new set of options ready for configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_uring::Builder;
+    ///
+    /// let mut rt = Builder::new().entries(32).build().unwrap();
+    /// assert_eq!(rt.block_on(async { 1 + 1 }), 2);
+    /// ```
+    pub fn new() -> Builder {
+        Builder {
+            entries: 256,
+            cq_entries: None,
+            sqpoll_idle: None,
+            sqpoll_cpu: None,
+            iopoll: false,
+            coop_taskrun: false,
+            single_issuer: false,
+            attach_wq: None,
+            fixed_files: 0,
+            shutdown_timeout: Duration::from_secs(1),
+        }
+    }
+
+    /// Sets the number of submission queue entries.
+    ///
+    /// The kernel rounds the value up to the next power of two. Unless
+    /// [`cq_entries`] is set, the completion queue is twice as large.
+    ///
+    /// [`cq_entries`]: Builder::cq_entries
+    pub fn entries(&mut self, entries: u32) -> &mut Builder {
+        self.entries = entries;
+        self
+    }
+
+    /// Sets the number of completion queue entries (`IORING_SETUP_CQSIZE`).
+    ///
+    /// The value must be greater than the number of submission queue entries.
+    pub fn cq_entries(&mut self, entries: u32) -> &mut Builder {
+        self.cq_entries = Some(entries);
+        self
+    }
+
+    /// Starts a kernel thread polling the submission queue
+    /// (`IORING_SETUP_SQPOLL`).
+    ///
+    /// The thread goes to sleep after being idle for `idle`, and is woken up
+    /// again on the next submission.
+    pub fn sqpoll(&mut self, idle: Duration) -> &mut Builder {
+        self.sqpoll_idle = Some(idle);
+        self
+    }
+
+    /// Binds the submission polling thread to the given CPU
+    /// (`IORING_SETUP_SQ_AFF`).
+    ///
+    /// This only has an effect when [`sqpoll`] is enabled.
+    ///
+    /// [`sqpoll`]: Builder::sqpoll
+    pub fn sqpoll_cpu(&mut self, cpu: u32) -> &mut Builder {
+        self.sqpoll_cpu = Some(cpu);
+        self
+    }
+
+    /// Performs busy-waiting for I/O completions (`IORING_SETUP_IOPOLL`).
+    ///
+    /// This is only usable on files opened with `O_DIRECT` on devices
+    /// supporting polling; other operations fail with `EOPNOTSUPP`.
+    pub fn iopoll(&mut self, iopoll: bool) -> &mut Builder {
+        self.iopoll = iopoll;
+        self
+    }
+
+    /// Avoids interrupting the runtime thread to run completion work
+    /// (`IORING_SETUP_COOP_TASKRUN`).
+    pub fn coop_taskrun(&mut self, coop_taskrun: bool) -> &mut Builder {
+        self.coop_taskrun = coop_taskrun;
+        self
+    }
+
+    /// Hints the kernel that only the runtime thread submits requests
+    /// (`IORING_SETUP_SINGLE_ISSUER`).
+    pub fn single_issuer(&mut self, single_issuer: bool) -> &mut Builder {
+        self.single_issuer = single_issuer;
+        self
+    }
+
+    /// Shares the asynchronous worker pool of an existing ring
+    /// (`IORING_SETUP_ATTACH_WQ`).
+    ///
+    /// `fd` is the ring file descriptor of another runtime, as returned by
+    /// its `AsRawFd` implementation.
+    pub fn attach_wq(&mut self, fd: RawFd) -> &mut Builder {
+        self.attach_wq = Some(fd);
+        self
+    }
+
+    /// Registers a sparse fixed-file table with `slots` entries
+    /// (`IORING_REGISTER_FILES`).
+    ///
+    /// Files opened with [`OpenOptions::fixed_file`] and connections accepted
+    /// with [`TcpListener::accept_fixed`] are installed into free slots of
+    /// the table, which saves the kernel a descriptor lookup per operation.
+    /// Without a table, or once it is full, these fail with `ENFILE`.
+    ///
+    /// [`OpenOptions::fixed_file`]: crate::fs::OpenOptions::fixed_file
+    /// [`TcpListener::accept_fixed`]: crate::net::TcpListener::accept_fixed
+    pub fn fixed_files(&mut self, slots: u32) -> &mut Builder {
+        self.fixed_files = slots;
+        self
+    }
+
+    /// Sets how long shutting down the runtime waits for cancelled operations
+    /// to complete.
+    ///
+    /// When the runtime is dropped or [`shutdown`] is called, every in-flight
+    /// operation is cancelled. Operations the kernel has not completed once
+    /// the timeout elapses are leaked. Defaults to one second.
+    ///
+    /// [`shutdown`]: Runtime::shutdown
+    pub fn shutdown_timeout(&mut self, timeout: Duration) -> &mut Builder {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Creates the configured `Runtime`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the kernel rejects the ring configuration, e.g.
+    /// because a setup flag is not supported.
+    pub fn build(&self) -> io::Result<Runtime> {
+        Runtime::with_builder(self)
+    }
+
+    /// Sets up the `io-uring` instance described by this builder.
+    pub(crate) fn build_uring(&self) -> io::Result<IoUring> {
+        let mut builder = IoUring::builder();
+
+        if let Some(entries) = self.cq_entries {
+            builder.setup_cqsize(entries);
+        }
+
+        if let Some(idle) = self.sqpoll_idle {
+            builder.setup_sqpoll(idle.as_millis().try_into().unwrap_or(u32::MAX));
+
+            if let Some(cpu) = self.sqpoll_cpu {
+                builder.setup_sqpoll_cpu(cpu);
+            }
+        }
+
+        if self.iopoll {
+            builder.setup_iopoll();
+        }
+
+        if self.coop_taskrun {
+            builder.setup_coop_taskrun();
+        }
+
+        if self.single_issuer {
+            builder.setup_single_issuer();
+        }
+
+        if let Some(fd) = self.attach_wq {
+            builder.setup_attach_wq(fd);
+        }
+
+        builder.build(self.entries)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +1,6 @@
 use async_task::{Runnable, Task};
 use atomic_waker::AtomicWaker;
+#[allow(deprecated)]
 use cache_padded::CachePadded;
 use concurrent_queue::ConcurrentQueue;
 use futures_lite::{
@@ -18,6 +19,12 @@ use std::{
 
 scoped_thread_local!(static CURRENT: LocalExecutor);
 
+/// Spawns a task on the current runtime.
+///
+/// # Panics
+///
+/// Panics if called outside of a runtime. Use [`Handle::spawn`] to spawn
+/// tasks from other threads.
 pub fn spawn<T: Future + 'static>(task: T) -> Task<T::Output> {
     CURRENT.with(|exec| exec.spawn(task))
 }
@@ -39,6 +46,7 @@ mod thread_id {
     }
 }
 
+#[allow(deprecated)]
 #[derive(Debug)]
 struct State {
     global_queue: ConcurrentQueue<Runnable>,
@@ -46,6 +54,16 @@ struct State {
     local_state: CachePadded<UnsafeCell<LocalState>>,
 }
 
+// SAFETY: `State` is shared with other threads through `Handle` and the
+// schedule functions, but those only call `push_global` and `wake`, which use
+// the thread-safe `global_queue` and `sleep_waker`. `local_state` holds
+// runnables of `!Send` futures and is only accessed by `push_local`, which
+// `schedule` calls on the executor thread only, and by `runnable` and the drop
+// of `LocalExecutor`, which is `!Send`. Runnables of `!Send` futures pushed to
+// `global_queue` are only run or dropped by the executor thread as well.
+unsafe impl Send for State {}
+unsafe impl Sync for State {}
+
 #[derive(Debug)]
 struct LocalState {
     ticks: usize,
@@ -53,6 +71,7 @@ struct LocalState {
 }
 
 impl State {
+    #[allow(deprecated)]
     fn new() -> Self {
         Self {
             global_queue: ConcurrentQueue::unbounded(),
@@ -71,7 +90,12 @@ impl State {
     }
 
     fn push_global(&self, runnable: Runnable) {
-        self.global_queue.push(runnable).unwrap();
+        if let Err(err) = self.global_queue.push(runnable) {
+            // The executor is gone. The task may hold `!Send` state, so it
+            // must not be dropped on this thread.
+            std::mem::forget(err.into_inner());
+            return;
+        }
         self.wake();
     }
 
@@ -86,17 +110,25 @@ impl State {
     }
 
     async fn runnable_with(&self, mut search: impl FnMut() -> Option<Runnable>) -> Runnable {
-        future::poll_fn(|cx| match search() {
-            None => {
-                self.sleep(cx.waker());
-                Poll::Pending
-            }
-            Some(r) => {
-                let local_state = unsafe { &mut *self.local_state.get() };
-                local_state.ticks += 1;
+        future::poll_fn(|cx| {
+            let runnable = match search() {
+                Some(r) => r,
+                None => {
+                    self.sleep(cx.waker());
 
-                Poll::Ready(r)
-            }
+                    // A task pushed by another thread before the waker was
+                    // registered did not wake anybody, so search again.
+                    match search() {
+                        Some(r) => r,
+                        None => return Poll::Pending,
+                    }
+                }
+            };
+
+            let local_state = unsafe { &mut *self.local_state.get() };
+            local_state.ticks += 1;
+
+            Poll::Ready(runnable)
         })
         .await
     }
@@ -171,12 +203,161 @@ impl LocalExecutor {
         future.or(run_forever).await
     }
 
-    fn schedule_fn(&self) -> impl Fn(Runnable) + 'static {
-        let state = self.state.clone();
-        let thread_id = self.thread_id;
+    /// Returns a `Handle` submitting tasks to this executor from any thread.
+    pub(crate) fn handle(&self) -> Handle {
+        Handle {
+            state: self.state.clone(),
+            thread_id: self.thread_id,
+        }
+    }
+
+    fn schedule_fn(&self) -> impl Fn(Runnable) + Send + Sync + 'static {
+        schedule_fn(self.state.clone(), self.thread_id)
+    }
+}
+
+impl Drop for LocalExecutor {
+    fn drop(&mut self) {
+        // Drop the remaining tasks on the executor thread. Dropping a task may
+        // wake others, e.g. by dropping the sender of a channel, so the queues
+        // are drained until no task is scheduled any more.
+        self.drain();
+
+        // Later wake-ups from other threads are discarded.
+        self.state.global_queue.close();
+        self.drain();
+    }
+}
 
-        move |runnable| {
-            state.schedule(runnable, thread_id);
+impl LocalExecutor {
+    fn drain(&self) {
+        loop {
+            let runnable = match self.state.global_queue.pop() {
+                Ok(runnable) => runnable,
+                Err(_) => {
+                    let local_state = unsafe { &mut *self.state.local_state.get() };
+                    match local_state.queue.pop_front() {
+                        Some(runnable) => runnable,
+                        None => return,
+                    }
+                }
+            };
+
+            // The queue is not borrowed while the task is dropped.
+            drop(runnable);
         }
     }
 }
+
+/// A handle to a running runtime.
+///
+/// A `Handle` can be cloned and sent to other threads, which use it to spawn
+/// tasks onto the runtime. Spawning through a handle wakes the runtime if it
+/// is idle.
+///
+/// Tasks only make progress while the runtime is inside
+/// [`Runtime::block_on`].
+///
+/// [`Runtime::block_on`]: crate::Runtime::block_on
+///
+/// # Examples
+///
+/// Hand the result of blocking work back to the runtime thread:
+///
+/// ```
+/// use async_uring::Runtime;
+///
+/// let mut rt = Runtime::new().unwrap();
+/// let handle = rt.handle();
+///
+/// let worker = std::thread::spawn(move || {
+///     let compressed = vec![42u8; 16];
+///     handle.spawn(async move { compressed.len() })
+/// });
+///
+/// let task = worker.join().unwrap();
+/// assert_eq!(rt.block_on(task), 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Handle {
+    state: Arc<State>,
+    thread_id: usize,
+}
+
+impl Handle {
+    /// Returns a handle to the runtime the caller is running on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime.
+    pub fn current() -> Handle {
+        Handle::try_current().expect("must be called from the context of an async-uring runtime")
+    }
+
+    /// Returns a handle to the runtime the caller is running on, or `None`
+    /// if called outside of a runtime.
+    pub fn try_current() -> Option<Handle> {
+        CURRENT.is_set().then(|| CURRENT.with(|exec| exec.handle()))
+    }
+
+    /// Spawns a future onto the runtime.
+    pub fn spawn<F>(&self, future: F) -> Task<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (runnable, task) =
+            async_task::spawn(future, schedule_fn(self.state.clone(), self.thread_id));
+        self.state.schedule(runnable, self.thread_id);
+        task
+    }
+
+    /// Spawns the future returned by `f` onto the runtime.
+    ///
+    /// `f` is sent to the runtime thread and called there, so the future
+    /// itself does not need to be `Send` and may use the `io-uring` types.
+    pub fn spawn_with<F, Fut>(&self, f: F) -> Task<Fut::Output>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let future = async move { f().await };
+
+        // Safety: until its first poll the future only holds `f`, which is
+        // `Send`. The runnable is scheduled on the runtime and then only
+        // polled by the runtime thread.
+        let (runnable, task) = unsafe {
+            async_task::spawn_unchecked(future, schedule_fn(self.state.clone(), self.thread_id))
+        };
+        self.state.schedule(runnable, self.thread_id);
+        task
+    }
+
+    /// Runs a future on the runtime and blocks the current thread until it
+    /// completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from the runtime thread itself, as it would never
+    /// make progress.
+    pub fn block_on_remote<F>(&self, future: F) -> F::Output
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        assert_ne!(
+            thread_id::current(),
+            Some(self.thread_id),
+            "`block_on_remote` cannot be called from the runtime thread"
+        );
+
+        future::block_on(self.spawn(future))
+    }
+}
+
+fn schedule_fn(state: Arc<State>, thread_id: usize) -> impl Fn(Runnable) + Send + Sync + 'static {
+    move |runnable| {
+        state.schedule(runnable, thread_id);
+    }
+}
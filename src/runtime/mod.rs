@@ -1,25 +1,79 @@
 use self::executor::LocalExecutor;
 use crate::driver::Driver;
 use futures_lite::FutureExt;
-use std::{future::Future, io};
+use std::{
+    future::Future,
+    io,
+    os::unix::io::{AsRawFd, RawFd},
+};
 
+mod builder;
 mod executor;
+mod sharded;
 
-pub use self::executor::spawn;
+pub use self::{
+    builder::Builder,
+    executor::{spawn, Handle},
+    sharded::ShardedRuntime,
+};
 
+/// An `io-uring` backed runtime.
+///
+/// The runtime owns a single ring and a single-threaded executor. Futures
+/// passed to [`block_on`] and tasks created with [`spawn`] run on the thread
+/// calling `block_on`.
+///
+/// A runtime is usually created through a [`Builder`], or implicitly by
+/// [`start`].
+///
+/// [`block_on`]: Runtime::block_on
+/// [`spawn`]: crate::spawn
+/// [`start`]: crate::start
+///
+/// # Examples
+///
+/// ```
+/// use async_uring::Runtime;
+///
+/// let mut rt = Runtime::new().unwrap();
+///
+/// assert_eq!(rt.block_on(async { 1 }), 1);
+/// assert_eq!(rt.block_on(async { 2 }), 2);
+/// ```
 pub struct Runtime {
-    driver: async_io::Async<Driver>,
+    // The driver shuts down first, completing every operation and so waking
+    // the tasks waiting on them, which the executor then drops along with
+    // the queued ones. An idle task is only reachable through its wakers, so
+    // it would otherwise be scheduled once the executor is gone and leaked.
     executor: LocalExecutor,
+    driver: async_io::Async<Driver>,
 }
 
 impl Runtime {
+    /// Creates a new runtime with the default configuration.
+    ///
+    /// See [`Builder`] for customizing the ring.
     pub fn new() -> io::Result<Self> {
+        Builder::new().build()
+    }
+
+    pub(crate) fn with_builder(builder: &Builder) -> io::Result<Self> {
         Ok(Self {
-            driver: async_io::Async::new(Driver::new()?)?,
             executor: LocalExecutor::new(),
+            driver: async_io::Async::new(Driver::new(builder)?)?,
         })
     }
 
+    /// Returns a handle to this runtime, used to spawn tasks from other
+    /// threads.
+    pub fn handle(&self) -> Handle {
+        self.executor.handle()
+    }
+
+    /// Runs a future to completion on the runtime.
+    ///
+    /// Tasks spawned while the future runs stay attached to the runtime and
+    /// are resumed by later calls to `block_on`.
     pub fn block_on<F>(&mut self, future: F) -> F::Output
     where
         F: Future,
@@ -40,4 +94,34 @@ impl Runtime {
             })
         })
     }
+
+    /// Shuts the runtime down and returns the number of leaked operations.
+    ///
+    /// Every in-flight operation is cancelled, and the runtime waits up to the
+    /// configured [`shutdown_timeout`] for the cancelled operations to
+    /// complete. Operations still in flight afterwards are leaked, as the
+    /// kernel may still access their buffers. All spawned tasks are then
+    /// dropped, including the ones waiting on an operation.
+    ///
+    /// Dropping the runtime does the same, without reporting leaks.
+    ///
+    /// [`shutdown_timeout`]: Builder::shutdown_timeout
+    pub fn shutdown(mut self) -> usize {
+        self.driver.get_mut().shutdown()
+    }
+}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        let driver = self.driver.get_mut();
+        if !driver.is_shut_down() {
+            driver.shutdown();
+        }
+    }
+}
+
+impl AsRawFd for Runtime {
+    fn as_raw_fd(&self) -> RawFd {
+        self.driver.get_ref().as_raw_fd()
+    }
 }
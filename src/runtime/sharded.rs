@@ -0,0 +1,281 @@
+use crate::runtime::{Builder, Handle};
+use async_task::Task;
+use atomic_waker::AtomicWaker;
+use std::{
+    future::Future,
+    io, mem,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    task::Poll,
+    thread,
+};
+
+/// A thread-per-core runtime.
+///
+/// A `ShardedRuntime` starts one worker thread per shard. Each worker owns its
+/// own ring and executor and is pinned to a CPU, so tasks never migrate
+/// between threads. Work is submitted to a specific shard with
+/// [`spawn_on`].
+///
+/// Sharded runtimes are created with [`Builder::build_sharded`]; every worker
+/// uses the ring configuration of the builder. Dropping the runtime behaves
+/// like [`join`].
+///
+/// [`join`]: ShardedRuntime::join
+/// [`spawn_on`]: ShardedRuntime::spawn_on
+///
+/// # Examples
+///
+/// ```
+/// use async_uring::Builder;
+///
+/// let rt = Builder::new().build_sharded(2).unwrap();
+///
+/// let tasks: Vec<_> = (0..rt.shards())
+///     .map(|shard| rt.spawn_on(shard, move || async move { shard * 2 }))
+///     .collect();
+///
+/// for (shard, task) in tasks.into_iter().enumerate() {
+///     assert_eq!(futures_lite::future::block_on(task), shard * 2);
+/// }
+///
+/// rt.join();
+/// ```
+pub struct ShardedRuntime {
+    shards: Vec<Shard>,
+}
+
+struct Shard {
+    handle: Handle,
+    state: Arc<ShardState>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+/// Shutdown bookkeeping shared between a shard handle and its worker.
+#[derive(Default)]
+struct ShardState {
+    /// Set once the runtime is joined or dropped.
+    shutdown: AtomicBool,
+
+    /// Number of tasks spawned with `spawn_on` that have not finished yet.
+    tasks: AtomicUsize,
+
+    /// Wakes the worker when `shutdown` or `tasks` change.
+    waker: AtomicWaker,
+}
+
+impl Builder {
+    /// Creates a [`ShardedRuntime`] with `shards` worker threads.
+    ///
+    /// The `n`-th worker is pinned to the `n`-th CPU the process may run on,
+    /// wrapping around if there are more shards than CPUs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidInput` error if `shards` is zero, and an error if a
+    /// worker fails to set up its ring or to pin itself. Workers started
+    /// before the failure are shut down.
+    pub fn build_sharded(&self, shards: usize) -> io::Result<ShardedRuntime> {
+        if shards == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "a sharded runtime needs at least one shard",
+            ));
+        }
+
+        let cpus = available_cpus()?;
+        let mut rt = ShardedRuntime {
+            shards: Vec::with_capacity(shards),
+        };
+
+        for n in 0..shards {
+            let builder = self.clone();
+            let cpu = cpus[n % cpus.len()];
+            let state = Arc::new(ShardState::default());
+            let (tx, rx) = mpsc::channel();
+
+            let thread = thread::Builder::new()
+                .name(format!("async-uring-{}", n))
+                .spawn({
+                    let state = state.clone();
+                    move || {
+                        let mut runtime = match pin_to_cpu(cpu).and_then(|_| builder.build()) {
+                            Ok(runtime) => runtime,
+                            Err(e) => {
+                                let _ = tx.send(Err(e));
+                                return;
+                            }
+                        };
+
+                        let _ = tx.send(Ok(runtime.handle()));
+                        runtime.block_on(state.stopped());
+                    }
+                })?;
+
+            let handle = rx
+                .recv()
+                .map_err(|_| io::Error::other("shard worker exited during startup"))??;
+
+            rt.shards.push(Shard {
+                handle,
+                state,
+                thread: Some(thread),
+            });
+        }
+
+        Ok(rt)
+    }
+}
+
+impl ShardedRuntime {
+    /// Returns the number of shards.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns a handle to the runtime of the given shard.
+    ///
+    /// Unlike [`spawn_on`], tasks spawned through the handle are not waited
+    /// for by [`join`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shard` is out of bounds.
+    ///
+    /// [`spawn_on`]: ShardedRuntime::spawn_on
+    /// [`join`]: ShardedRuntime::join
+    pub fn handle(&self, shard: usize) -> &Handle {
+        &self.shards[shard].handle
+    }
+
+    /// Spawns a task on the given shard.
+    ///
+    /// `f` is sent to the worker thread and called there to create the
+    /// future, so the future does not need to be `Send` and may use
+    /// thread-local types such as [`TcpStream`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shard` is out of bounds.
+    ///
+    /// [`TcpStream`]: crate::net::TcpStream
+    pub fn spawn_on<F, Fut>(&self, shard: usize, f: F) -> Task<Fut::Output>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let shard = &self.shards[shard];
+        let guard = TaskGuard::new(shard.state.clone());
+
+        shard.handle.spawn_with(move || async move {
+            let _guard = guard;
+            f().await
+        })
+    }
+
+    /// Waits for all tasks spawned with [`spawn_on`] to complete, then stops
+    /// the workers.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the panic of a worker thread, if any.
+    ///
+    /// [`spawn_on`]: ShardedRuntime::spawn_on
+    pub fn join(mut self) {
+        if let Err(panic) = self.shutdown() {
+            std::panic::resume_unwind(panic);
+        }
+    }
+
+    fn shutdown(&mut self) -> thread::Result<()> {
+        for shard in &self.shards {
+            shard.state.shutdown.store(true, Ordering::Release);
+            shard.state.wake();
+        }
+
+        let mut result = Ok(());
+        for shard in &mut self.shards {
+            if let Some(thread) = shard.thread.take() {
+                if let Err(panic) = thread.join() {
+                    result = result.and(Err(panic));
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Drop for ShardedRuntime {
+    fn drop(&mut self) {
+        let _ = self.shutdown();
+    }
+}
+
+impl ShardState {
+    fn wake(&self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Completes once shutdown was requested and all tasks have finished.
+    async fn stopped(&self) {
+        futures_lite::future::poll_fn(|cx| {
+            self.waker.register(cx.waker());
+
+            if self.shutdown.load(Ordering::Acquire) && self.tasks.load(Ordering::Acquire) == 0 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+/// Keeps a shard alive until the task owning the guard is dropped.
+struct TaskGuard(Arc<ShardState>);
+
+impl TaskGuard {
+    fn new(state: Arc<ShardState>) -> TaskGuard {
+        state.tasks.fetch_add(1, Ordering::AcqRel);
+        TaskGuard(state)
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        if self.0.tasks.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.wake();
+        }
+    }
+}
+
+/// Returns the CPUs the current process is allowed to run on.
+fn available_cpus() -> io::Result<Vec<usize>> {
+    let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+    syscall!(sched_getaffinity(
+        0,
+        mem::size_of::<libc::cpu_set_t>(),
+        &mut set
+    ))?;
+
+    Ok((0..libc::CPU_SETSIZE as usize)
+        .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+        .collect())
+}
+
+/// Pins the calling thread to `cpu`.
+fn pin_to_cpu(cpu: usize) -> io::Result<()> {
+    let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+    unsafe { libc::CPU_SET(cpu, &mut set) };
+    syscall!(sched_setaffinity(
+        0,
+        mem::size_of::<libc::cpu_set_t>(),
+        &set
+    ))?;
+    Ok(())
+}
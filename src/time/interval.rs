@@ -0,0 +1,83 @@
+use crate::time::{sleep_until, Sleep};
+use futures_lite::Stream;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// Creates a new `Interval` yielding every `period`.
+///
+/// The first tick completes immediately.
+///
+/// # Panics
+///
+/// Panics if `period` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use async_uring::time::interval;
+/// use std::time::Duration;
+///
+/// async_uring::start(async {
+///     let mut interval = interval(Duration::from_millis(10));
+///
+///     interval.tick().await; // completes immediately
+///     interval.tick().await; // completes after 10ms
+/// });
+/// ```
+pub fn interval(period: Duration) -> Interval {
+    assert!(period > Duration::ZERO, "`period` must be non-zero");
+
+    Interval {
+        sleep: sleep_until(Instant::now()),
+        period,
+    }
+}
+
+/// Yields at a fixed period, created by [`interval`].
+///
+/// If a tick is missed because the interval was not polled in time, the next
+/// tick completes immediately and the following ones are scheduled a full
+/// `period` later, instead of firing a burst of ticks to catch up.
+pub struct Interval {
+    sleep: Sleep,
+    period: Duration,
+}
+
+impl Interval {
+    /// Completes at the next tick, returning the instant it was scheduled for.
+    pub async fn tick(&mut self) -> Instant {
+        futures_lite::future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    /// Polls for the next tick.
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        ready!(Pin::new(&mut self.sleep).poll(cx));
+
+        let deadline = self.sleep.deadline();
+        let now = Instant::now();
+        let mut next = deadline + self.period;
+        if next <= now {
+            next = now + self.period;
+        }
+        self.sleep.reset(next);
+
+        Poll::Ready(deadline)
+    }
+
+    /// Returns the period of the interval.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+impl Stream for Interval {
+    type Item = Instant;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Instant>> {
+        self.poll_tick(cx).map(Some)
+    }
+}
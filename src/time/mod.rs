@@ -0,0 +1,18 @@
+//! Utilities for tracking time.
+//!
+//! Timers are submitted to the ring as `IORING_OP_TIMEOUT` operations, so they
+//! are driven by the same thread as the rest of the I/O and do not need a
+//! separate timer thread.
+//!
+//! * [`sleep`] and [`sleep_until`] wait for a point in time.
+//! * [`interval`] yields at a fixed period.
+//! * [`timeout`] bounds the execution time of a future.
+
+mod interval;
+pub use interval::{interval, Interval};
+
+mod sleep;
+pub use sleep::{sleep, sleep_until, Sleep};
+
+mod timeout;
+pub use timeout::{timeout, Elapsed};
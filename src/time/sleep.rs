@@ -0,0 +1,103 @@
+use crate::driver::{Op, Timeout};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// Waits until `duration` has elapsed.
+///
+/// # Examples
+///
+/// ```
+/// use async_uring::time::sleep;
+/// use std::time::{Duration, Instant};
+///
+/// async_uring::start(async {
+///     let start = Instant::now();
+///     sleep(Duration::from_millis(10)).await;
+///     assert!(start.elapsed() >= Duration::from_millis(10));
+/// });
+/// ```
+pub fn sleep(duration: Duration) -> Sleep {
+    sleep_until(Instant::now() + duration)
+}
+
+/// Waits until `deadline` is reached.
+pub fn sleep_until(deadline: Instant) -> Sleep {
+    Sleep { deadline, op: None }
+}
+
+/// Future returned by [`sleep`] and [`sleep_until`].
+///
+/// The timeout is submitted when the future is first polled. Dropping the
+/// future before it completes removes the timeout from the ring.
+pub struct Sleep {
+    deadline: Instant,
+    op: Option<Op<Timeout>>,
+}
+
+impl Sleep {
+    /// Returns the instant at which the future completes.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns `true` once the deadline was reached.
+    pub fn is_elapsed(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+
+    /// Resets the future to complete at `deadline` instead.
+    pub fn reset(&mut self, deadline: Instant) {
+        self.cancel();
+        self.deadline = deadline;
+    }
+
+    fn cancel(&mut self) {
+        if let Some(mut op) = self.op.take() {
+            op.remove();
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let me = &mut *self;
+
+        loop {
+            match &mut me.op {
+                Some(op) => {
+                    // The kernel reports `ETIME` once the timeout fired.
+                    let _ = ready!(Pin::new(op).poll(cx));
+                    me.op = None;
+
+                    // The timer can only fire early if it was cancelled, e.g.
+                    // while shutting down. Arm it again for the remainder.
+                    if me.is_elapsed() {
+                        return Poll::Ready(());
+                    }
+                }
+                None => {
+                    let now = Instant::now();
+                    if now >= me.deadline {
+                        return Poll::Ready(());
+                    }
+
+                    me.op = Some(
+                        Op::timeout(me.deadline - now).expect("failed to submit timeout operation"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
@@ -0,0 +1,49 @@
+use crate::time::sleep;
+use futures_lite::FutureExt;
+use std::{error::Error, fmt, future::Future, io, time::Duration};
+
+/// Requires `future` to complete within `duration`.
+///
+/// If the future does not complete in time, it is dropped and `Elapsed` is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use async_uring::time::{sleep, timeout};
+/// use std::time::Duration;
+///
+/// async_uring::start(async {
+///     let res = timeout(Duration::from_millis(10), sleep(Duration::from_secs(10))).await;
+///     assert!(res.is_err());
+///
+///     let res = timeout(Duration::from_secs(10), async { 42 }).await;
+///     assert_eq!(res.unwrap(), 42);
+/// });
+/// ```
+pub async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+    let elapsed = async {
+        sleep(duration).await;
+        Err(Elapsed(()))
+    };
+
+    async { Ok(future.await) }.or(elapsed).await
+}
+
+/// Error returned by [`timeout`] when the deadline elapsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "deadline has elapsed".fmt(f)
+    }
+}
+
+impl Error for Elapsed {}
+
+impl From<Elapsed> for io::Error {
+    fn from(err: Elapsed) -> io::Error {
+        io::Error::new(io::ErrorKind::TimedOut, err)
+    }
+}
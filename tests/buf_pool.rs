@@ -0,0 +1,146 @@
+use async_uring::{
+    buf::BufPool,
+    net::{TcpStream, UdpSocket},
+};
+use futures_lite::StreamExt;
+use std::io::Write;
+
+#[test]
+fn tcp_read_pooled() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let pool = BufPool::new(2, 8).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.buf_size(), 8);
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+
+        peer.write_all(b"hello").unwrap();
+        let first = stream.read_pooled(&pool).await.unwrap().unwrap();
+        assert_eq!(&first[..], b"hello");
+
+        peer.write_all(b"0123456789").unwrap();
+        let second = stream.read_pooled(&pool).await.unwrap().unwrap();
+        assert_eq!(&second[..], b"01234567");
+        assert_ne!(first.buf_id(), second.buf_id());
+
+        // Both buffers are in use.
+        let err = stream.read_pooled(&pool).await.unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOBUFS));
+
+        // Dropping a buffer provides it again.
+        drop(first);
+        let third = stream.read_pooled(&pool).await.unwrap().unwrap();
+        assert_eq!(&third[..], b"89");
+
+        drop((second, third));
+        drop(peer);
+        assert!(stream.read_pooled(&pool).await.unwrap().is_none());
+    });
+}
+
+#[test]
+fn udp_recv_pooled() {
+    async_uring::start(async {
+        let pool = BufPool::new(4, 64).unwrap();
+
+        let sender = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+        // Learn the address of the socket from a datagram it sends.
+        socket.connect(sender.local_addr().unwrap()).await.unwrap();
+        let (res, _) = socket.write(&b"hi"[..]).await;
+        res.unwrap();
+        let (_, addr) = sender.recv_from(&mut [0; 2]).unwrap();
+        for msg in [&b"one"[..], b"two", b""] {
+            sender.send_to(msg, addr).unwrap();
+        }
+
+        let one = socket.recv_pooled(&pool).await.unwrap();
+        let two = socket.recv_pooled(&pool).await.unwrap();
+        assert_eq!(&one[..], b"one");
+        assert_eq!(&two[..], b"two");
+
+        // An empty datagram takes no buffer of the pool.
+        let empty = socket.recv_pooled(&pool).await.unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.buf_id(), None);
+    });
+}
+
+#[test]
+fn tcp_recv_multishot() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let pool = BufPool::new(2, 8).unwrap();
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+        let mut bufs = stream.recv_multishot(&pool);
+
+        peer.write_all(b"hello").unwrap();
+        let first = bufs.next().await.unwrap().unwrap();
+        assert_eq!(&first[..], b"hello");
+
+        peer.write_all(b"0123456789").unwrap();
+        let second = bufs.next().await.unwrap().unwrap();
+        assert_eq!(&second[..], b"01234567");
+
+        // Both buffers are in use, receiving resumes once one is dropped.
+        let err = bufs.next().await.unwrap().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOBUFS));
+        drop(first);
+        let third = bufs.next().await.unwrap().unwrap();
+        assert_eq!(&third[..], b"89");
+
+        drop((second, third));
+        drop(peer);
+        assert!(bufs.next().await.is_none());
+        assert!(bufs.next().await.is_none());
+    });
+}
+
+#[test]
+fn udp_recv_msg_multishot() {
+    async_uring::start(async {
+        let pool = BufPool::new(4, 64).unwrap();
+
+        let sender = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+        // Learn the address of the socket from a datagram it sends.
+        socket.connect(sender.local_addr().unwrap()).await.unwrap();
+        let (res, _) = socket.write(&b"hi"[..]).await;
+        res.unwrap();
+        let (_, addr) = sender.recv_from(&mut [0; 2]).unwrap();
+
+        let mut datagrams = socket.recv_msg_multishot(&pool);
+        for msg in [&b"one"[..], b"two", b"three"] {
+            sender.send_to(msg, addr).unwrap();
+        }
+
+        for msg in [&b"one"[..], b"two", b"three"] {
+            let (buf, from) = datagrams.next().await.unwrap().unwrap();
+            assert_eq!(&buf[..], msg);
+            assert_eq!(from, sender.local_addr().unwrap());
+        }
+    });
+}
+
+#[test]
+fn pools_get_distinct_groups() {
+    async_uring::start(async {
+        let first = BufPool::new(1, 16).unwrap();
+        let second = BufPool::new(1, 16).unwrap();
+        assert_ne!(first.buf_group(), second.buf_group());
+
+        let group = first.buf_group();
+        drop(first);
+        assert_eq!(BufPool::new(1, 16).unwrap().buf_group(), group);
+    });
+}
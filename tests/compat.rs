@@ -0,0 +1,123 @@
+use async_uring::{
+    fs::OpenOptions,
+    net::{TcpListener, TcpStream, UnixStream},
+};
+use futures_lite::io::{
+    self, AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, SeekFrom,
+};
+
+#[test]
+fn tcp_stream() {
+    async_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut client = TcpStream::connect(addr).await.unwrap().compat();
+        let (server, _) = listener.accept().await.unwrap();
+        let mut server = server.compat();
+
+        client.write_all(b"hello\nworld\n").await.unwrap();
+        // Closing flushes the buffer, then shuts down the write half.
+        client.close().await.unwrap();
+
+        let mut line = String::new();
+        server.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "hello\n");
+
+        let mut rest = String::new();
+        server.read_to_string(&mut rest).await.unwrap();
+        assert_eq!(rest, "world\n");
+    });
+}
+
+#[test]
+fn copy_large() {
+    async_uring::start(async {
+        let (a, b) = UnixStream::pair().unwrap();
+        let (mut a, mut b) = (a.compat(), b.compat());
+
+        let data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+        let writer = async_uring::spawn({
+            let data = data.clone();
+            async move {
+                io::copy(&data[..], &mut a).await.unwrap();
+                a.close().await.unwrap();
+            }
+        });
+
+        let mut received = Vec::new();
+        b.read_to_end(&mut received).await.unwrap();
+        writer.await;
+        assert_eq!(received, data);
+    });
+}
+
+#[test]
+fn file_seek() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("compat.txt");
+
+    async_uring::start(async {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)
+            .await
+            .unwrap();
+        let mut file = file.compat();
+        file.write_all(b"hello world").await.unwrap();
+
+        // Seeking flushes the writes.
+        assert_eq!(file.seek(SeekFrom::Start(6)).await.unwrap(), 6);
+        let mut word = String::new();
+        file.read_to_string(&mut word).await.unwrap();
+        assert_eq!(word, "world");
+
+        // Writes go to the cursor, after what was consumed.
+        assert_eq!(file.seek(SeekFrom::Start(0)).await.unwrap(), 0);
+        let mut first = [0; 5];
+        file.read_exact(&mut first).await.unwrap();
+        assert_eq!(&first, b"hello");
+        file.write_all(b"_").await.unwrap();
+
+        assert_eq!(file.seek(SeekFrom::End(-5)).await.unwrap(), 6);
+        assert_eq!(file.seek(SeekFrom::Current(-6)).await.unwrap(), 0);
+        assert!(file.seek(SeekFrom::Current(-1)).await.is_err());
+
+        let mut all = String::new();
+        file.read_to_string(&mut all).await.unwrap();
+        assert_eq!(all, "hello_world");
+
+        file.into_inner().close().await.unwrap();
+    });
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"hello_world");
+}
+
+#[test]
+fn fixed_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("compat-fixed");
+    std::fs::write(&path, b"hello world").unwrap();
+
+    let mut rt = async_uring::Builder::new().fixed_files(1).build().unwrap();
+    rt.block_on(async {
+        let file = OpenOptions::new()
+            .read(true)
+            .fixed_file(true)
+            .open(&path)
+            .await
+            .unwrap();
+        let mut file = file.compat();
+
+        let mut all = String::new();
+        file.read_to_string(&mut all).await.unwrap();
+        assert_eq!(all, "hello world");
+
+        // The size of a fixed file cannot be queried.
+        let err = file.seek(SeekFrom::End(0)).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        assert_eq!(file.seek(SeekFrom::Start(6)).await.unwrap(), 6);
+    });
+}
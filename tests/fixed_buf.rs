@@ -0,0 +1,132 @@
+use async_uring::{buf::fixed::FixedBufRegistry, fs::File};
+use std::io;
+
+#[test]
+fn fixed_file_read_write() {
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+
+    async_uring::start(async {
+        let registry = FixedBufRegistry::new([Vec::with_capacity(16), Vec::with_capacity(16)]);
+        registry.register().unwrap();
+
+        let file = File::create(tempfile.path()).await.unwrap();
+
+        let mut buf = registry.check_out(0).unwrap();
+        assert!(registry.check_out(0).is_none());
+        buf.extend_from_slice(b"hello world");
+
+        let (res, buf) = file.write_fixed_at(buf, 0).await;
+        assert_eq!(res.unwrap(), 11);
+        drop(buf);
+
+        // The buffer keeps its contents when checked in.
+        let buf = registry.check_out(0).unwrap();
+        assert_eq!(&buf[..], b"hello world");
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        let (res, buf) = file.read_fixed_at(registry.check_out(1).unwrap(), 6).await;
+        assert_eq!(res.unwrap(), 5);
+        assert_eq!(&buf[..], b"world");
+        assert_eq!(buf.buf_index(), 1);
+    });
+}
+
+#[test]
+fn fixed_buf_requires_registration() {
+    async_uring::start(async {
+        let registry = FixedBufRegistry::new([Vec::with_capacity(16)]);
+        registry.register().unwrap();
+        assert_eq!(
+            registry.register().unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+        registry.unregister().unwrap();
+
+        let tempfile = tempfile::NamedTempFile::new().unwrap();
+        let file = File::open(tempfile.path()).await.unwrap();
+        let (res, _) = file.read_fixed_at(registry.check_out(0).unwrap(), 0).await;
+        assert!(res.is_err());
+    });
+}
+
+#[test]
+fn fixed_tcp_stream() {
+    use async_uring::net::TcpStream;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let registry = FixedBufRegistry::new([Vec::with_capacity(16)]);
+        registry.register().unwrap();
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+
+        io::Write::write_all(&mut peer, b"ping").unwrap();
+        let (res, mut buf) = stream.read_fixed(registry.check_out(0).unwrap()).await;
+        assert_eq!(res.unwrap(), 4);
+        assert_eq!(&buf[..], b"ping");
+
+        buf.clear();
+        buf.extend_from_slice(b"pong");
+        let (res, _) = stream.write_fixed(buf).await;
+        assert_eq!(res.unwrap(), 4);
+
+        let mut reply = [0; 4];
+        io::Read::read_exact(&mut peer, &mut reply).unwrap();
+        assert_eq!(&reply, b"pong");
+    });
+}
+
+#[test]
+fn fixed_send_zc() {
+    use async_uring::net::TcpStream;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let registry = FixedBufRegistry::new([Vec::with_capacity(16)]);
+        registry.register().unwrap();
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+
+        let mut buf = registry.check_out(0).unwrap();
+        buf.extend_from_slice(b"zero copy");
+        let (res, buf) = stream.send_zc_fixed(buf).await;
+        assert_eq!(res.unwrap(), 9);
+        assert_eq!(buf.buf_index(), 0);
+
+        let mut received = [0; 9];
+        io::Read::read_exact(&mut peer, &mut received).unwrap();
+        assert_eq!(&received, b"zero copy");
+    });
+}
+
+#[test]
+fn fixed_udp_send_zc() {
+    use async_uring::net::UdpSocket;
+
+    let receiver = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+
+    async_uring::start(async {
+        let registry = FixedBufRegistry::new([Vec::with_capacity(16)]);
+        registry.register().unwrap();
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        socket.connect(addr).await.unwrap();
+
+        let mut buf = registry.check_out(0).unwrap();
+        buf.extend_from_slice(b"datagram");
+        let (res, buf) = socket.send_zc_fixed(buf).await;
+        assert_eq!(res.unwrap(), 8);
+        assert_eq!(buf.buf_index(), 0);
+
+        let mut received = [0; 16];
+        let n = receiver.recv(&mut received).unwrap();
+        assert_eq!(&received[..n], b"datagram");
+    });
+}
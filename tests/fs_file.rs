@@ -75,6 +75,25 @@ fn basic_write_all() {
     });
 }
 
+#[test]
+fn vectored_read_write() {
+    async_uring::start(async {
+        let tempfile = tempfile();
+
+        let file = File::create(tempfile.path()).await.unwrap();
+        let (res, bufs) = file.writev_at(vec![&HELLO[..5], &HELLO[5..]], 0).await;
+        assert_eq!(res.unwrap(), HELLO.len());
+        assert_eq!(bufs.len(), 2);
+
+        let file = File::open(tempfile.path()).await.unwrap();
+        let bufs = vec![Vec::with_capacity(6), Vec::with_capacity(16)];
+        let (res, bufs) = file.readv_at(bufs, 0).await;
+        assert_eq!(res.unwrap(), HELLO.len());
+        assert_eq!(&bufs[0][..], &HELLO[..6]);
+        assert_eq!(&bufs[1][..], &HELLO[6..]);
+    });
+}
+
 #[test]
 fn cancel_read() {
     async_uring::start(async {
@@ -109,7 +128,7 @@ fn cancel_read() {
 fn drop_open() {
     async_uring::start(async {
         let tempfile = tempfile();
-        let _ = File::create(tempfile.path());
+        drop(File::create(tempfile.path()));
 
         // Do something else
         let file = File::create(tempfile.path()).await.unwrap();
@@ -175,4 +194,56 @@ fn assert_invalid_fd(fd: RawFd) {
         Err(ref e) if e.raw_os_error() == Some(libc::EBADF) => {}
         res => panic!("{:?}", res),
     }
+
+    // The fd is already closed, don't close it again.
+    std::mem::forget(f);
+}
+
+#[test]
+fn fixed_file() {
+    use async_uring::fs::OpenOptions;
+
+    let tempfile = tempfile();
+
+    let mut rt = async_uring::Builder::new().fixed_files(2).build().unwrap();
+    rt.block_on(async {
+        // More files than slots, closing a file releases its slot.
+        for _ in 0..4 {
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .fixed_file(true)
+                .open(tempfile.path())
+                .await
+                .unwrap();
+            assert_eq!(file.as_raw_fd(), -1);
+
+            let (res, _) = file.write_at(HELLO, 0).await;
+            assert_eq!(res.unwrap(), HELLO.len());
+            file.sync_all().await.unwrap();
+
+            read_hello(&file).await;
+            file.close().await.unwrap();
+        }
+    });
+
+    rt.block_on(async {
+        let file = File::open(tempfile.path()).await.unwrap();
+        assert_ne!(file.as_raw_fd(), -1);
+
+        let file = file.into_fixed().await.unwrap();
+        assert_eq!(file.as_raw_fd(), -1);
+        assert!(format!("{:?}", file).starts_with("File { fixed: "));
+        read_hello(&file).await;
+    });
+
+    // Without a table, there is no slot to install the file into.
+    async_uring::start(async {
+        let res = OpenOptions::new()
+            .read(true)
+            .fixed_file(true)
+            .open(tempfile.path())
+            .await;
+        assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::ENFILE));
+    });
 }
@@ -33,3 +33,182 @@ fn spawn_a_task() {
         assert_eq!(2, *cell.borrow());
     });
 }
+
+#[test]
+fn block_on_repeatedly() {
+    let mut rt = async_uring::Builder::new().entries(8).build().unwrap();
+
+    let task = rt.block_on(futures_lite::future::poll_fn(|_| {
+        std::task::Poll::Ready(async_uring::spawn(async { 1 }))
+    }));
+    assert_eq!(rt.block_on(task), 1);
+    assert_eq!(rt.block_on(async { 2 }), 2);
+}
+
+#[test]
+fn builder_setup_flags() {
+    use async_uring::fs::File;
+    use std::{os::unix::io::AsRawFd, time::Duration};
+
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(tempfile.path(), b"hello").unwrap();
+
+    let first = async_uring::Builder::new()
+        .entries(64)
+        .cq_entries(256)
+        .coop_taskrun(true)
+        .single_issuer(true)
+        .build()
+        .unwrap();
+
+    let attached = async_uring::Builder::new()
+        .attach_wq(first.as_raw_fd())
+        .build()
+        .unwrap();
+
+    let sqpoll = async_uring::Builder::new()
+        .entries(64)
+        .sqpoll(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    for mut rt in [first, attached, sqpoll] {
+        rt.block_on(async {
+            let file = File::open(tempfile.path()).await.unwrap();
+            let (res, buf) = file.read_at(vec![0; 5], 0).await;
+            assert_eq!(res.unwrap(), 5);
+            assert_eq!(buf, b"hello");
+        });
+    }
+}
+
+#[test]
+fn sharded_runtime_runs_tasks_per_shard() {
+    use std::{cell::Cell, rc::Rc};
+
+    let rt = async_uring::Builder::new()
+        .entries(8)
+        .build_sharded(2)
+        .unwrap();
+    assert_eq!(rt.shards(), 2);
+
+    let tasks: Vec<_> = (0..rt.shards())
+        .map(|shard| {
+            rt.spawn_on(shard, move || {
+                // `!Send` state is fine, the future is created on the worker.
+                let cell = Rc::new(Cell::new(shard));
+                async move {
+                    let c = cell.clone();
+                    async_uring::spawn(async move { c.set(c.get() + 10) }).await;
+                    (
+                        cell.get(),
+                        std::thread::current().name().unwrap().to_owned(),
+                    )
+                }
+            })
+        })
+        .collect();
+
+    for (shard, task) in tasks.into_iter().enumerate() {
+        let (value, thread) = futures_lite::future::block_on(task);
+        assert_eq!(value, shard + 10);
+        assert_eq!(thread, format!("async-uring-{}", shard));
+    }
+
+    rt.join();
+}
+
+#[test]
+fn sharded_runtime_join_waits_for_tasks() {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    let done = Arc::new(AtomicBool::new(false));
+    let rt = async_uring::Builder::new().build_sharded(1).unwrap();
+
+    let d = done.clone();
+    rt.spawn_on(0, move || async move {
+        async_uring::time::sleep(std::time::Duration::from_millis(50)).await;
+        d.store(true, Ordering::SeqCst);
+    })
+    .detach();
+
+    rt.join();
+    assert!(done.load(Ordering::SeqCst));
+}
+
+#[test]
+fn sharded_runtime_rejects_zero_shards() {
+    let err = async_uring::Builder::new().build_sharded(0).err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn handle_spawns_from_other_threads() {
+    use async_uring::Handle;
+
+    let mut rt = async_uring::Runtime::new().unwrap();
+    let handle = rt.handle();
+
+    let worker = std::thread::spawn(move || {
+        // Blocks until the runtime thread drives the task below.
+        let n = handle.block_on_remote(async { 40 });
+        handle.spawn(async move { n + 2 })
+    });
+
+    let value = rt.block_on(async {
+        // Keep the runtime busy until the worker handed its task over.
+        while !worker.is_finished() {
+            futures_lite::future::yield_now().await;
+        }
+        worker.join().unwrap().await
+    });
+    assert_eq!(value, 42);
+
+    rt.block_on(async {
+        let handle = Handle::current();
+        let task = handle.spawn_with(|| {
+            // `!Send` state held across an await point.
+            let rc = std::rc::Rc::new(());
+            async move {
+                futures_lite::future::yield_now().await;
+                Handle::try_current().is_some() && std::rc::Rc::strong_count(&rc) == 1
+            }
+        });
+        assert!(task.await);
+    });
+    assert!(Handle::try_current().is_none());
+}
+
+#[test]
+fn shutdown_cancels_pending_operations() {
+    use async_uring::net::TcpListener;
+    use std::time::{Duration, Instant};
+
+    let mut rt = async_uring::Builder::new()
+        .shutdown_timeout(Duration::from_secs(10))
+        .build()
+        .unwrap();
+
+    let addr = rt.block_on(async {
+        // Never gets a connection.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        async_uring::spawn(async move {
+            let _ = listener.accept().await;
+        })
+        .detach();
+
+        futures_lite::future::yield_now().await;
+        addr
+    });
+
+    let start = Instant::now();
+    assert_eq!(rt.shutdown(), 0);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // The task waiting on the accept was dropped, closing the listener.
+    std::net::TcpListener::bind(addr).unwrap();
+}
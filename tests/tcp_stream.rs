@@ -0,0 +1,556 @@
+use async_uring::net::TcpStream;
+use std::{io, time::Duration};
+
+#[test]
+fn read_with_timeout() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+
+        // Nothing is sent, the kernel cancels the read.
+        let (res, buf) = stream
+            .read_with_timeout(Vec::with_capacity(16), Duration::from_millis(20))
+            .await;
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::TimedOut);
+        assert_eq!(buf.capacity(), 16);
+
+        io::Write::write_all(&mut peer, b"hello").unwrap();
+
+        let (res, buf) = stream.read_with_timeout(buf, Duration::from_secs(10)).await;
+        assert_eq!(res.unwrap(), 5);
+        assert_eq!(&buf[..], b"hello");
+
+        let (res, _) = stream
+            .write_with_timeout(b"world".as_slice(), Duration::from_secs(10))
+            .await;
+        assert_eq!(res.unwrap(), 5);
+    });
+}
+
+#[test]
+fn drop_accept_cancels_operation() {
+    use async_uring::time::timeout;
+    use futures_lite::{future::poll_fn, pin};
+    use std::{future::Future, task::Poll, time::Instant};
+
+    let start = Instant::now();
+
+    let addr = async_uring::start(async {
+        let listener = async_uring::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        {
+            let accept = listener.accept();
+            pin!(accept);
+            poll_fn(|cx| {
+                assert!(accept.as_mut().poll(cx).is_pending());
+                Poll::Ready(())
+            })
+            .await;
+        }
+
+        // Were the dropped accept still in flight, it would take the
+        // connection and the next accept would wait for another one.
+        let mut peer = std::net::TcpStream::connect(addr).unwrap();
+        let (stream, _) = timeout(Duration::from_secs(5), listener.accept())
+            .await
+            .expect("the connection was taken by the dropped accept")
+            .unwrap();
+
+        let (res, _) = stream.write(&b"hello"[..]).await;
+        assert_eq!(res.unwrap(), 5);
+
+        let mut buf = [0; 5];
+        io::Read::read_exact(&mut peer, &mut buf).unwrap();
+        assert_eq!(&buf, b"hello");
+
+        // A pending accept is cancelled on shutdown.
+        async_uring::spawn(async move {
+            let _ = listener.accept().await;
+        })
+        .detach();
+        futures_lite::future::yield_now().await;
+        addr
+    });
+
+    // Without cancellation, shutting down the runtime waits for a connection
+    // that never arrives.
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // The task was dropped along with the runtime, closing the listener.
+    std::net::TcpListener::bind(addr).unwrap();
+}
+
+#[test]
+fn drop_read_and_stream() {
+    use futures_lite::{future::poll_fn, pin};
+    use std::{future::Future, task::Poll};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+
+        {
+            let read = stream.read(vec![0; 16]);
+            pin!(read);
+            poll_fn(|cx| {
+                assert!(read.as_mut().poll(cx).is_pending());
+                Poll::Ready(())
+            })
+            .await;
+        }
+
+        // The cancelled read holds the last reference to the socket, which is
+        // closed once the read completes.
+        drop(stream);
+        async_uring::time::sleep(Duration::from_millis(20)).await;
+
+        // Without cancellation, the read would wait for data and keep the
+        // socket open.
+        peer.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut buf = [0; 16];
+        assert_eq!(io::Read::read(&mut peer, &mut buf).unwrap(), 0);
+    });
+}
+
+#[test]
+fn accept_fixed() {
+    use async_uring::net::TcpListener;
+
+    let mut rt = async_uring::Builder::new().fixed_files(4).build().unwrap();
+    rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut peer = std::net::TcpStream::connect(addr).unwrap();
+        let (stream, _) = listener.accept_fixed().await.unwrap();
+
+        io::Write::write_all(&mut peer, b"hello").unwrap();
+        let (res, buf) = stream.read(Vec::with_capacity(16)).await;
+        assert_eq!(res.unwrap(), 5);
+        assert_eq!(&buf[..], b"hello");
+
+        let (res, _) = stream.write(&b"world"[..]).await;
+        assert_eq!(res.unwrap(), 5);
+
+        let mut reply = [0; 5];
+        io::Read::read_exact(&mut peer, &mut reply).unwrap();
+        assert_eq!(&reply, b"world");
+    });
+}
+
+#[test]
+fn incoming() {
+    use async_uring::net::TcpListener;
+    use futures_lite::StreamExt;
+
+    async_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut incoming = listener.incoming();
+
+        let mut peers: Vec<_> = (0..3)
+            .map(|_| std::net::TcpStream::connect(addr).unwrap())
+            .collect();
+
+        for i in 0..3u8 {
+            let stream = incoming.next().await.unwrap().unwrap();
+            let (res, _) = stream.write(vec![i]).await;
+            assert_eq!(res.unwrap(), 1);
+        }
+
+        for (i, peer) in peers.iter_mut().enumerate() {
+            let mut byte = [0; 1];
+            io::Read::read_exact(peer, &mut byte).unwrap();
+            assert_eq!(byte[0] as usize, i);
+        }
+    });
+}
+
+#[test]
+fn incoming_slow_consumer() {
+    use async_uring::net::TcpListener;
+    use futures_lite::StreamExt;
+
+    async_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut incoming = listener.incoming();
+        let first = std::net::TcpStream::connect(addr).unwrap();
+        incoming.next().await.unwrap().unwrap();
+
+        // More connections than the stream queues arrive while nobody polls
+        // it, so the multishot accept is stopped and armed again later.
+        let peers: Vec<_> = (0..200)
+            .map(|_| std::net::TcpStream::connect(addr).unwrap())
+            .collect();
+        async_uring::time::sleep(Duration::from_millis(100)).await;
+
+        for _ in 0..peers.len() {
+            incoming.next().await.unwrap().unwrap();
+        }
+        drop(first);
+    });
+}
+
+#[test]
+fn incoming_cancel() {
+    use async_uring::net::TcpListener;
+    use futures_lite::{future, StreamExt};
+
+    async_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut incoming = listener.incoming();
+        assert!(future::poll_once(incoming.next()).await.is_none());
+
+        // The stream ends instead of yielding the cancellation.
+        incoming.cancel();
+        assert!(incoming.next().await.is_none());
+
+        // The connection waits for the next accept.
+        let _peer = std::net::TcpStream::connect(addr).unwrap();
+        listener.accept().await.unwrap();
+    });
+}
+
+#[test]
+fn incoming_dropped() {
+    use async_uring::net::TcpListener;
+    use futures_lite::{future, StreamExt};
+
+    let mut rt = async_uring::Runtime::new().unwrap();
+    rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Arm the multishot accept, then drop it before any connection.
+        let mut incoming = listener.incoming();
+        assert!(future::poll_once(incoming.next()).await.is_none());
+        drop(incoming);
+
+        // The cancelled accept must not take the connection.
+        let mut peer = std::net::TcpStream::connect(addr).unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+
+        let (res, _) = stream.write(&b"hello"[..]).await;
+        assert_eq!(res.unwrap(), 5);
+
+        let mut buf = [0; 5];
+        io::Read::read_exact(&mut peer, &mut buf).unwrap();
+        assert_eq!(&buf, b"hello");
+
+        // A stream left armed is cancelled on shutdown.
+        async_uring::spawn(async move {
+            let _ = listener.incoming().next().await;
+        })
+        .detach();
+
+        future::yield_now().await;
+    });
+
+    assert_eq!(rt.shutdown(), 0);
+}
+
+#[test]
+fn send_zc() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+
+        let data: Vec<u8> = (0..64 * 1024).map(|i| i as u8).collect();
+        let reader = std::thread::spawn(move || {
+            let mut received = Vec::new();
+            io::Read::read_to_end(&mut peer, &mut received).unwrap();
+            received
+        });
+
+        let mut sent = 0;
+        while sent < data.len() {
+            let (res, _) = stream.send_zc(data[sent..].to_vec()).await;
+            sent += res.unwrap();
+        }
+        drop(stream);
+
+        assert_eq!(reader.join().unwrap(), data);
+    });
+}
+
+#[test]
+fn recv_with_peek() {
+    use async_uring::net::RecvFlags;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+        io::Write::write_all(&mut peer, b"GET /").unwrap();
+
+        let (res, buf) = stream
+            .recv_with_flags(Vec::with_capacity(3), RecvFlags::PEEK)
+            .await;
+        assert_eq!(res.unwrap(), 3);
+        assert_eq!(&buf[..], b"GET");
+
+        // The peeked data is still queued.
+        let (res, buf) = stream
+            .recv_with_flags(Vec::with_capacity(5), RecvFlags::WAITALL)
+            .await;
+        assert_eq!(res.unwrap(), 5);
+        assert_eq!(&buf[..], b"GET /");
+    });
+}
+
+#[test]
+fn send_nosignal_to_closed_peer() {
+    use async_uring::net::SendFlags;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        drop(listener.accept().unwrap());
+
+        // The first sends may still succeed, until the peer's reset arrives.
+        let err = loop {
+            let (res, _) = stream
+                .send_with_flags(&b"data"[..], SendFlags::NOSIGNAL)
+                .await;
+            match res {
+                Ok(_) => async_uring::time::sleep(Duration::from_millis(10)).await,
+                Err(e) => break e,
+            }
+        };
+        assert!(matches!(
+            err.kind(),
+            io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+        ));
+    });
+}
+
+#[test]
+fn writev() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    async_uring::start(async {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+
+        let header = b"len:5\n".to_vec();
+        let body = b"hello".to_vec();
+        let (res, bufs) = stream.writev(vec![header, body]).await;
+        assert_eq!(res.unwrap(), 11);
+        assert_eq!(bufs[1], b"hello");
+
+        let mut received = [0; 11];
+        io::Read::read_exact(&mut peer, &mut received).unwrap();
+        assert_eq!(&received, b"len:5\nhello");
+    });
+}
+
+#[test]
+fn addresses_and_options() {
+    use async_uring::net::TcpListener;
+
+    async_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        assert_ne!(addr.port(), 0);
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (peer, peer_addr) = listener.accept().await.unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), addr);
+        assert_eq!(stream.local_addr().unwrap(), peer_addr);
+        assert_eq!(peer.peer_addr().unwrap(), peer_addr);
+
+        stream.set_nodelay(true).unwrap();
+        assert!(stream.nodelay().unwrap());
+
+        stream.set_ttl(42).unwrap();
+        assert_eq!(stream.ttl().unwrap(), 42);
+
+        stream.set_keepalive(true).unwrap();
+        assert!(stream.keepalive().unwrap());
+
+        stream.set_linger(Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(1)));
+
+        stream.set_recv_buffer_size(64 * 1024).unwrap();
+        assert!(stream.recv_buffer_size().unwrap() >= 64 * 1024);
+    });
+}
+
+#[test]
+fn tcp_socket() {
+    use async_uring::net::TcpSocket;
+
+    async_uring::start(async {
+        let socket = TcpSocket::new_v4().unwrap();
+        socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        assert!(!socket.reuseport().unwrap());
+        let addr = socket.local_addr().unwrap();
+        let listener = socket.listen(16).unwrap();
+
+        // Without `SO_REUSEPORT`, the port conflict is detected.
+        let other = TcpSocket::new_v4().unwrap();
+        let err = other.bind(addr).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AddrInUse);
+
+        let source = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let source_addr = source.local_addr().unwrap();
+        drop(source);
+
+        let socket = TcpSocket::new_v4().unwrap();
+        socket.set_nodelay(true).unwrap();
+        socket.bind(source_addr).unwrap();
+        let stream = socket.connect(addr).await.unwrap();
+        assert!(stream.nodelay().unwrap());
+
+        let (_peer, peer_addr) = listener.accept().await.unwrap();
+        assert_eq!(peer_addr, source_addr);
+    });
+}
+
+#[test]
+fn shutdown_write() {
+    use async_uring::net::TcpListener;
+    use std::net::Shutdown;
+
+    async_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (peer, _) = listener.accept().await.unwrap();
+
+        let (res, _) = stream.write(&b"request"[..]).await;
+        assert_eq!(res.unwrap(), 7);
+        stream.shutdown(Shutdown::Write).await.unwrap();
+
+        let (res, buf) = peer.read(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap()], b"request");
+        let (res, _) = peer.read(buf).await;
+        assert_eq!(res.unwrap(), 0);
+
+        // The other half is still open.
+        let (res, _) = peer.write(&b"response"[..]).await;
+        assert_eq!(res.unwrap(), 8);
+        let (res, buf) = stream.read(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap()], b"response");
+
+        peer.close().await.unwrap();
+        stream.close().await.unwrap();
+        listener.close().await.unwrap();
+
+        let err = TcpStream::connect(addr).await.err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    });
+}
+
+#[test]
+fn connect_resolves_and_falls_back() {
+    use async_uring::net::TcpListener;
+    use std::net::SocketAddr;
+
+    async_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let stream = TcpStream::connect(format!("localhost:{}", addr.port()))
+            .await
+            .unwrap();
+        assert_eq!(stream.peer_addr().unwrap().port(), addr.port());
+
+        let stream = TcpStream::connect(("127.0.0.1", addr.port()))
+            .await
+            .unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), addr);
+
+        // Nothing listens on the first address, the second one is tried.
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed_addr = closed.local_addr().unwrap();
+        drop(closed);
+        let stream = TcpStream::connect(&[closed_addr, addr][..]).await.unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), addr);
+
+        let err = TcpStream::connect(&[closed_addr][..]).await.err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+
+        let no_addrs: &[SocketAddr] = &[];
+        let err = TcpStream::connect(no_addrs).await.err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        // Binding is synchronous and does not look host names up.
+        let err = TcpListener::bind("localhost:0").err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    });
+}
+
+#[test]
+fn dual_stack_listener() {
+    use async_uring::net::TcpSocket;
+
+    // IPv6 may be disabled in the test environment.
+    if std::net::TcpListener::bind("[::1]:0").is_err() {
+        return;
+    }
+
+    async_uring::start(async {
+        let socket = TcpSocket::new_v6().unwrap();
+        socket.set_only_v6(false).unwrap();
+        socket.bind("[::]:0".parse().unwrap()).unwrap();
+        let listener = socket.listen(16).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let (_peer, peer_addr) = listener.accept().await.unwrap();
+        assert_eq!(peer_addr.port(), stream.local_addr().unwrap().port());
+
+        let socket = TcpSocket::new_v6().unwrap();
+        socket.set_only_v6(true).unwrap();
+        assert!(socket.only_v6().unwrap());
+        socket.bind("[::]:0".parse().unwrap()).unwrap();
+        let port = socket.local_addr().unwrap().port();
+        let _listener = socket.listen(16).unwrap();
+
+        let err = TcpStream::connect(("127.0.0.1", port)).await.err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    });
+}
+
+#[test]
+fn close_waits_for_incoming() {
+    use async_uring::net::TcpListener;
+
+    async_uring::start(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = listener.incoming();
+
+        // The stream still uses the descriptor, so the close waits for it.
+        let close = async_uring::spawn(listener.close());
+        async_uring::time::sleep(Duration::from_millis(20)).await;
+        assert!(std::net::TcpStream::connect(addr).is_ok());
+
+        drop(incoming);
+        async_uring::time::timeout(Duration::from_secs(5), close)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(std::net::TcpStream::connect(addr).is_err());
+    });
+}
@@ -0,0 +1,74 @@
+use async_uring::time::{interval, sleep, sleep_until, timeout};
+use futures_lite::{future::poll_fn, pin, StreamExt};
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn sleep_waits_for_duration() {
+    async_uring::start(async {
+        let start = Instant::now();
+        sleep(Duration::from_millis(20)).await;
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        let deadline = Instant::now() + Duration::from_millis(20);
+        sleep_until(deadline).await;
+        assert!(Instant::now() >= deadline);
+
+        // Deadlines in the past complete immediately.
+        sleep_until(start).await;
+    });
+}
+
+#[test]
+fn drop_sleep_removes_timeout() {
+    let start = Instant::now();
+
+    async_uring::start(async {
+        poll_once(sleep(Duration::from_secs(30))).await;
+    });
+
+    // Shutting down the runtime waits for in-flight operations, which would
+    // take 30 seconds if the timeout was not removed.
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn interval_ticks() {
+    async_uring::start(async {
+        let start = Instant::now();
+        let mut interval = interval(Duration::from_millis(10));
+
+        let first = interval.tick().await;
+        assert!(first.duration_since(start) < Duration::from_millis(10));
+
+        let second = interval.next().await.unwrap();
+        assert_eq!(second - first, Duration::from_millis(10));
+        assert!(Instant::now() >= second);
+    });
+}
+
+#[test]
+fn timeout_elapses() {
+    async_uring::start(async {
+        let res = timeout(Duration::from_millis(10), sleep(Duration::from_secs(30))).await;
+        let err = std::io::Error::from(res.unwrap_err());
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+
+        let res = timeout(Duration::from_secs(30), async { 1 }).await;
+        assert_eq!(res.unwrap(), 1);
+    });
+}
+
+async fn poll_once(future: impl Future) {
+    use std::task::Poll;
+
+    pin!(future);
+
+    poll_fn(|cx| {
+        assert!(future.as_mut().poll(cx).is_pending());
+        Poll::Ready(())
+    })
+    .await;
+}
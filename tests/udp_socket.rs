@@ -0,0 +1,210 @@
+use async_uring::net::UdpSocket;
+
+#[test]
+fn send_to_zc() {
+    let receiver = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+
+    async_uring::start(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+        let (res, buf) = socket.send_to_zc(b"datagram".to_vec(), addr).await;
+        assert_eq!(res.unwrap(), 8);
+        assert_eq!(buf, b"datagram");
+
+        let mut received = [0; 16];
+        let (n, _) = receiver.recv_from(&mut received).unwrap();
+        assert_eq!(&received[..n], b"datagram");
+    });
+}
+
+#[test]
+fn send_msg() {
+    let receiver = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+
+    async_uring::start(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+        let (res, bufs) = socket
+            .send_msg(vec![&b"head"[..], b"-", b"body"], Some(addr))
+            .await;
+        assert_eq!(res.unwrap(), 9);
+        assert_eq!(bufs.len(), 3);
+
+        // Connected sockets need no address.
+        socket.connect(addr).await.unwrap();
+        let (res, _) = socket.send_msg(vec![&b"again"[..]], None).await;
+        assert_eq!(res.unwrap(), 5);
+
+        let mut received = [0; 16];
+        let n = receiver.recv(&mut received).unwrap();
+        assert_eq!(&received[..n], b"head-body");
+        let n = receiver.recv(&mut received).unwrap();
+        assert_eq!(&received[..n], b"again");
+    });
+}
+
+#[test]
+fn recv_msg() {
+    use async_uring::net::ControlMessages;
+
+    let sender = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+
+    async_uring::start(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+        // Learn the address of the socket from a datagram it sends.
+        socket.connect(sender.local_addr().unwrap()).await.unwrap();
+        let (res, _) = socket.write(&b"hi"[..]).await;
+        res.unwrap();
+        let (_, addr) = sender.recv_from(&mut [0; 2]).unwrap();
+        sender.send_to(b"datagram", addr).unwrap();
+
+        let control = ControlMessages::with_capacity(64);
+        let (res, (buf, control)) = socket.recv_msg(Vec::with_capacity(16), control).await;
+        let (n, from) = res.unwrap();
+        assert_eq!(n, 8);
+        assert_eq!(&buf[..], b"datagram");
+        assert_eq!(from, sender.local_addr().unwrap());
+
+        // No control message was enabled on the socket.
+        assert!(control.is_empty());
+    });
+}
+
+#[test]
+fn send_segments() {
+    let receiver = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+
+    async_uring::start(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+        let (res, _) = socket
+            .send_segments(&b"aaaabbbbcc"[..], 4, Some(addr))
+            .await;
+        assert_eq!(res.unwrap(), 10);
+
+        let mut buf = [0; 16];
+        for expected in [&b"aaaa"[..], b"bbbb", b"cc"] {
+            let n = receiver.recv(&mut buf).unwrap();
+            assert_eq!(&buf[..n], expected);
+        }
+    });
+}
+
+#[test]
+fn recv_gro() {
+    async_uring::start(async {
+        let receiver = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = receiver.local_addr().unwrap();
+        receiver.set_gro(true).unwrap();
+
+        let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let (res, _) = sender
+            .send_segments(&b"aaaabbbbcccc"[..], 4, Some(addr))
+            .await;
+        assert_eq!(res.unwrap(), 12);
+
+        // The datagrams may or may not be coalesced.
+        let mut received = Vec::new();
+        while received.len() < 12 {
+            let (res, buf) = receiver.recv_gro(Vec::with_capacity(64)).await;
+            let (n, _, segment_size) = res.unwrap();
+            assert_eq!(segment_size, 4);
+            assert_eq!(n % 4, 0);
+            received.extend_from_slice(&buf);
+        }
+        assert_eq!(received, b"aaaabbbbcccc");
+    });
+}
+
+#[test]
+fn addresses_and_options() {
+    use std::net::Ipv4Addr;
+
+    async_uring::start(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        assert_ne!(addr.port(), 0);
+        assert!(socket.peer_addr().is_err());
+
+        let peer = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let peer_addr = peer.local_addr().unwrap();
+        socket.connect(peer_addr).await.unwrap();
+        assert_eq!(socket.peer_addr().unwrap(), peer_addr);
+
+        socket.set_broadcast(true).unwrap();
+        assert!(socket.broadcast().unwrap());
+
+        socket.set_ttl(7).unwrap();
+        assert_eq!(socket.ttl().unwrap(), 7);
+
+        socket.set_multicast_ttl_v4(3).unwrap();
+        assert_eq!(socket.multicast_ttl_v4().unwrap(), 3);
+
+        socket.set_multicast_loop_v4(false).unwrap();
+        assert!(!socket.multicast_loop_v4().unwrap());
+
+        socket.set_send_buffer_size(64 * 1024).unwrap();
+        assert!(socket.send_buffer_size().unwrap() >= 64 * 1024);
+        socket.set_recv_buffer_size(64 * 1024).unwrap();
+        assert!(socket.recv_buffer_size().unwrap() >= 64 * 1024);
+
+        let group = Ipv4Addr::new(239, 255, 0, 1);
+        if socket
+            .join_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+            .is_ok()
+        {
+            socket
+                .leave_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+                .unwrap();
+        }
+    });
+}
+
+#[test]
+fn ipv6_multicast_options() {
+    // IPv6 may be disabled in the test environment.
+    if std::net::UdpSocket::bind("[::1]:0").is_err() {
+        return;
+    }
+
+    async_uring::start(async {
+        let socket = UdpSocket::bind("[::1]:0").await.unwrap();
+
+        socket.set_multicast_loop_v6(false).unwrap();
+        assert!(!socket.multicast_loop_v6().unwrap());
+
+        socket.set_multicast_hops_v6(4).unwrap();
+        assert_eq!(socket.multicast_hops_v6().unwrap(), 4);
+    });
+}
+
+#[test]
+fn dual_stack() {
+    use std::net::{Ipv6Addr, SocketAddrV6};
+
+    // IPv6 may be disabled in the test environment.
+    if std::net::UdpSocket::bind("[::1]:0").is_err() {
+        return;
+    }
+
+    async_uring::start(async {
+        let any = SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0);
+
+        let socket = UdpSocket::bind_v6(any, false).unwrap();
+        assert!(!socket.only_v6().unwrap());
+        let port = socket.local_addr().unwrap().port();
+
+        let sender = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender.send_to(b"v4", ("127.0.0.1", port)).unwrap();
+        let (res, buf) = socket.recv_from(vec![0; 16]).await;
+        let (n, _) = res.unwrap();
+        assert_eq!(&buf[..n], b"v4");
+
+        let socket = UdpSocket::bind_v6(any, true).unwrap();
+        assert!(socket.only_v6().unwrap());
+    });
+}
@@ -0,0 +1,94 @@
+use async_uring::net::{UnixDatagram, UnixSocketAddr};
+
+#[test]
+fn send_to_recv_from() {
+    let dir = tempfile::tempdir().unwrap();
+    let server_path = dir.path().join("server.sock");
+    let client_path = dir.path().join("client.sock");
+
+    async_uring::start(async {
+        let server = UnixDatagram::bind(&server_path).unwrap();
+        let client = UnixDatagram::bind(&client_path).unwrap();
+        let server_addr = UnixSocketAddr::from_pathname(&server_path).unwrap();
+
+        let (res, _) = client.send_to(&b"ping"[..], &server_addr).await;
+        assert_eq!(res.unwrap(), 4);
+
+        let (res, buf) = server.recv_from(vec![0; 16]).await;
+        let (n, from) = res.unwrap();
+        assert_eq!(&buf[..n], b"ping");
+        assert_eq!(from.as_pathname(), Some(&*client_path));
+
+        // Reply to the sender, then talk over a connected socket.
+        let (res, _) = server.send_to(&b"pong"[..], &from).await;
+        assert_eq!(res.unwrap(), 4);
+        client.connect(&server_path).await.unwrap();
+        assert_eq!(
+            client.peer_addr().unwrap().as_pathname(),
+            Some(&*server_path)
+        );
+
+        let (res, buf) = client.read(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap()], b"pong");
+        let (res, _) = client.write(&b"again"[..]).await;
+        assert_eq!(res.unwrap(), 5);
+        let (res, buf) = server.recv_from(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap().0], b"again");
+    });
+}
+
+#[test]
+fn abstract_namespace() {
+    let name = format!("async-uring-test-{}", std::process::id());
+    let addr = UnixSocketAddr::from_abstract_name(&name).unwrap();
+    assert_eq!(addr.as_abstract_name(), Some(name.as_bytes()));
+    assert!(addr.as_pathname().is_none());
+
+    async_uring::start(async {
+        let server = UnixDatagram::bind_addr(&addr).unwrap();
+        assert_eq!(
+            server.local_addr().unwrap().as_abstract_name(),
+            Some(name.as_bytes())
+        );
+
+        // An unbound sender is unnamed.
+        let client = UnixDatagram::unbound().unwrap();
+        let (res, _) = client.send_to(&b"log line"[..], &addr).await;
+        assert_eq!(res.unwrap(), 8);
+
+        let (res, buf) = server.recv_from(vec![0; 16]).await;
+        let (n, from) = res.unwrap();
+        assert_eq!(&buf[..n], b"log line");
+        assert!(from.is_unnamed());
+    });
+}
+
+#[test]
+fn pair() {
+    async_uring::start(async {
+        let (a, b) = UnixDatagram::pair().unwrap();
+
+        for msg in [&b"one"[..], &b"two"[..]] {
+            let (res, _) = a.write(msg).await;
+            assert_eq!(res.unwrap(), msg.len());
+        }
+
+        // Datagram boundaries are preserved.
+        let (res, buf) = b.read(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap()], b"one");
+        let (res, buf) = b.read(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap()], b"two");
+    });
+}
+
+#[test]
+fn buffer_sizes() {
+    async_uring::start(async {
+        let (socket, _) = UnixDatagram::pair().unwrap();
+
+        socket.set_send_buffer_size(64 * 1024).unwrap();
+        assert!(socket.send_buffer_size().unwrap() >= 64 * 1024);
+        socket.set_recv_buffer_size(64 * 1024).unwrap();
+        assert!(socket.recv_buffer_size().unwrap() >= 64 * 1024);
+    });
+}
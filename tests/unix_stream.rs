@@ -0,0 +1,218 @@
+use async_uring::net::{UnixListener, UnixStream};
+use futures_lite::StreamExt;
+
+#[test]
+fn incoming() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("incoming.sock");
+
+    async_uring::start(async {
+        let listener = UnixListener::bind(&path).unwrap();
+        let mut incoming = listener.incoming();
+
+        for _ in 0..2 {
+            let peer = UnixStream::connect(&path).await.unwrap();
+            let stream = incoming.next().await.unwrap().unwrap();
+
+            let (res, _) = peer.write(&b"ping"[..]).await;
+            assert_eq!(res.unwrap(), 4);
+
+            let (res, buf) = stream.read(Vec::with_capacity(4)).await;
+            assert_eq!(res.unwrap(), 4);
+            assert_eq!(&buf[..], b"ping");
+        }
+    });
+}
+
+#[test]
+fn pass_fd() {
+    use async_uring::net::ControlMessages;
+    use std::{
+        io::{Read, Seek, Write},
+        os::unix::io::{AsRawFd, FromRawFd},
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pass_fd.sock");
+
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(b"passed").unwrap();
+    file.rewind().unwrap();
+
+    async_uring::start(async {
+        let listener = UnixListener::bind(&path).unwrap();
+        let sender = UnixStream::connect(&path).await.unwrap();
+        let (receiver, _) = listener.accept().await.unwrap();
+
+        let mut control = ControlMessages::new();
+        control.push_scm_rights(&[file.as_raw_fd()]);
+        let (res, (_, control)) = sender
+            .send_msg_with_control(vec![&b"fd"[..]], control)
+            .await;
+        assert_eq!(res.unwrap(), 2);
+        assert_eq!(control.iter().count(), 1);
+
+        let control = ControlMessages::with_capacity(ControlMessages::space(4));
+        let (res, (buf, control)) = receiver.recv_msg(Vec::with_capacity(8), control).await;
+        assert_eq!(res.unwrap(), 2);
+        assert_eq!(&buf[..], b"fd");
+        assert!(!control.is_truncated());
+
+        let fds: Vec<_> = control.iter().filter_map(|msg| msg.scm_rights()).collect();
+        assert_eq!(fds.len(), 1);
+        assert_eq!(fds[0].len(), 1);
+
+        let mut received = unsafe { std::fs::File::from_raw_fd(fds[0][0]) };
+        let mut contents = String::new();
+        received.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "passed");
+    });
+}
+
+#[test]
+fn addresses() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("addresses.sock");
+
+    async_uring::start(async {
+        let listener = UnixListener::bind(&path).unwrap();
+        assert_eq!(listener.local_addr().unwrap().as_pathname(), Some(&*path));
+
+        let stream = UnixStream::connect(&path).await.unwrap();
+        let (_peer, _) = listener.accept().await.unwrap();
+
+        assert_eq!(stream.peer_addr().unwrap().as_pathname(), Some(&*path));
+        assert!(stream.local_addr().unwrap().is_unnamed());
+    });
+}
+
+#[test]
+fn shutdown_write() {
+    use std::net::Shutdown;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shutdown.sock");
+
+    async_uring::start(async {
+        let listener = UnixListener::bind(&path).unwrap();
+        let stream = UnixStream::connect(&path).await.unwrap();
+        let (peer, _) = listener.accept().await.unwrap();
+
+        stream.shutdown(Shutdown::Write).await.unwrap();
+        let (res, _) = peer.read(vec![0; 4]).await;
+        assert_eq!(res.unwrap(), 0);
+
+        let (res, _) = peer.write(&b"pong"[..]).await;
+        assert_eq!(res.unwrap(), 4);
+        let (res, buf) = stream.read(vec![0; 4]).await;
+        assert_eq!(&buf[..res.unwrap()], b"pong");
+
+        stream.close().await.unwrap();
+    });
+}
+
+#[test]
+fn abstract_namespace() {
+    use async_uring::net::UnixSocketAddr;
+
+    let name = format!("async-uring-stream-{}", std::process::id());
+    let addr = UnixSocketAddr::from_abstract_name(&name).unwrap();
+
+    async_uring::start(async {
+        let listener = UnixListener::bind_addr(&addr).unwrap();
+        let stream = UnixStream::connect_addr(&addr).await.unwrap();
+        let (_peer, _) = listener.accept().await.unwrap();
+
+        assert_eq!(
+            stream.peer_addr().unwrap().as_abstract_name(),
+            Some(name.as_bytes())
+        );
+    });
+}
+
+#[test]
+fn pair() {
+    async_uring::start(async {
+        let (a, b) = UnixStream::pair().unwrap();
+        assert!(a.peer_addr().unwrap().is_unnamed());
+
+        let (res, _) = a.write(&b"ping"[..]).await;
+        assert_eq!(res.unwrap(), 4);
+        let (res, buf) = b.read(vec![0; 4]).await;
+        assert_eq!(&buf[..res.unwrap()], b"ping");
+    });
+}
+
+#[test]
+fn seqpacket() {
+    use async_uring::net::UnixSocketAddr;
+
+    let dir = tempfile::tempdir().unwrap();
+    let addr = UnixSocketAddr::from_pathname(dir.path().join("seqpacket.sock")).unwrap();
+
+    async_uring::start(async {
+        let listener = UnixListener::bind_seqpacket(&addr).unwrap();
+        let stream = UnixStream::connect_seqpacket(&addr).await.unwrap();
+        let (peer, _) = listener.accept().await.unwrap();
+
+        for msg in [&b"first"[..], &b"second"[..]] {
+            let (res, _) = stream.write(msg).await;
+            assert_eq!(res.unwrap(), msg.len());
+        }
+
+        // Each read returns a single message.
+        let (res, buf) = peer.read(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap()], b"first");
+        let (res, buf) = peer.read(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap()], b"second");
+
+        let (a, b) = UnixStream::pair_seqpacket().unwrap();
+        let (res, _) = a.write(&b"x"[..]).await;
+        assert_eq!(res.unwrap(), 1);
+        let (res, buf) = b.read(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap()], b"x");
+    });
+}
+
+#[test]
+fn listener_removes_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("removed.sock");
+    let kept = dir.path().join("kept.sock");
+
+    async_uring::start(async {
+        let listener = UnixListener::bind(&path).unwrap();
+        assert!(path.exists());
+        drop(listener);
+        assert!(!path.exists());
+
+        // The path can be bound again.
+        let listener = UnixListener::bind(&path).unwrap();
+        listener.close().await.unwrap();
+        assert!(!path.exists());
+
+        let mut listener = UnixListener::bind(&kept).unwrap();
+        listener.set_remove_on_drop(false);
+        drop(listener);
+        assert!(kept.exists());
+    });
+}
+
+#[test]
+fn accept_peer_addr_and_cred() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cred.sock");
+
+    async_uring::start(async {
+        let listener = UnixListener::bind(&path).unwrap();
+        let stream = UnixStream::connect(&path).await.unwrap();
+        let (peer, peer_addr) = listener.accept().await.unwrap();
+        assert!(peer_addr.is_unnamed());
+
+        let cred = peer.peer_cred().unwrap();
+        assert_eq!(cred.pid(), std::process::id() as libc::pid_t);
+        assert_eq!(cred.uid(), unsafe { libc::getuid() });
+        assert_eq!(cred.gid(), unsafe { libc::getgid() });
+        assert_eq!(stream.peer_cred().unwrap(), cred);
+    });
+}